@@ -0,0 +1,190 @@
+use crate::addon_transport::AddonTransport;
+use crate::constants::{ADDON_RESOURCE_CACHE_SIZE, ADDON_RESOURCE_CACHE_TTL};
+use crate::runtime::{Env, EnvError, TryEnvFuture};
+use crate::types::addon::{Manifest, ResourcePath, ResourceRequest, ResourceResponse};
+use chrono::{DateTime, Duration, Utc};
+use futures::future::{LocalBoxFuture, Shared};
+use futures::{future, FutureExt};
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use url::Url;
+
+type SharedResourceFuture = Shared<LocalBoxFuture<'static, Result<ResourceResponse, EnvError>>>;
+
+struct CacheEntry {
+    response: ResourceResponse,
+    expires: DateTime<Utc>,
+    stale_revalidate: Duration,
+    stale_error: Duration,
+    last_used: u64,
+}
+
+impl CacheEntry {
+    fn new(response: ResourceResponse, now: DateTime<Utc>, last_used: u64) -> Self {
+        let cache_hints = response.cache_hints();
+        let max_age = cache_hints
+            .cache_max_age
+            .map(|max_age| Duration::seconds(max_age.into()))
+            .unwrap_or(*ADDON_RESOURCE_CACHE_TTL);
+        let stale_revalidate = Duration::seconds(cache_hints.stale_revalidate.unwrap_or(0).into());
+        let stale_error = Duration::seconds(cache_hints.stale_error.unwrap_or(0).into());
+        CacheEntry {
+            response,
+            expires: now + max_age,
+            stale_revalidate,
+            stale_error,
+            last_used,
+        }
+    }
+    fn is_fresh(&self, now: DateTime<Utc>) -> bool {
+        self.expires > now
+    }
+    fn is_revalidatable(&self, now: DateTime<Utc>) -> bool {
+        self.expires + self.stale_revalidate > now
+    }
+    fn is_usable_on_error(&self, now: DateTime<Utc>) -> bool {
+        self.expires + self.stale_error > now
+    }
+    fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires + cmp::max(self.stale_revalidate, self.stale_error) <= now
+    }
+}
+
+enum CacheLookup {
+    Fresh(ResourceResponse),
+    Stale(ResourceResponse),
+    Miss,
+}
+
+#[derive(Default)]
+struct ResourceCache {
+    entries: HashMap<ResourceRequest, CacheEntry>,
+    in_flight: HashMap<ResourceRequest, SharedResourceFuture>,
+    clock: u64,
+}
+
+impl ResourceCache {
+    fn tick(&mut self) -> u64 {
+        self.clock = self.clock.wrapping_add(1);
+        self.clock
+    }
+    fn get(&mut self, request: &ResourceRequest, now: DateTime<Utc>) -> CacheLookup {
+        let last_used = self.tick();
+        match self.entries.get_mut(request) {
+            Some(entry) if entry.is_fresh(now) => {
+                entry.last_used = last_used;
+                CacheLookup::Fresh(entry.response.to_owned())
+            }
+            Some(entry) if entry.is_revalidatable(now) => {
+                entry.last_used = last_used;
+                CacheLookup::Stale(entry.response.to_owned())
+            }
+            _ => CacheLookup::Miss,
+        }
+    }
+    fn get_on_error(
+        &self,
+        request: &ResourceRequest,
+        now: DateTime<Utc>,
+    ) -> Option<ResourceResponse> {
+        self.entries
+            .get(request)
+            .filter(|entry| entry.is_usable_on_error(now))
+            .map(|entry| entry.response.to_owned())
+    }
+    fn insert(&mut self, request: ResourceRequest, response: ResourceResponse, now: DateTime<Utc>) {
+        self.entries.retain(|_, entry| !entry.is_expired(now));
+        if self.entries.len() >= ADDON_RESOURCE_CACHE_SIZE && !self.entries.contains_key(&request) {
+            if let Some(least_used) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(request, _)| request.to_owned())
+            {
+                self.entries.remove(&least_used);
+            };
+        };
+        let last_used = self.tick();
+        self.entries
+            .insert(request, CacheEntry::new(response, now, last_used));
+    }
+}
+
+thread_local! {
+    static RESOURCE_CACHE: RefCell<ResourceCache> = Default::default();
+}
+
+// Drops all cached responses, e.g. when addons are installed or the user logs out
+pub fn clear_addon_resource_cache() {
+    RESOURCE_CACHE.with(|cache| cache.borrow_mut().entries.clear());
+}
+
+pub struct AddonCachedTransport<E: Env> {
+    transport_url: Url,
+    transport: Box<dyn AddonTransport>,
+    env: PhantomData<E>,
+}
+
+impl<E: Env + 'static> AddonCachedTransport<E> {
+    pub fn new(transport_url: Url, transport: Box<dyn AddonTransport>) -> Self {
+        AddonCachedTransport {
+            transport_url,
+            transport,
+            env: PhantomData,
+        }
+    }
+    fn fetch(&self, request: &ResourceRequest) -> SharedResourceFuture {
+        let in_flight = RESOURCE_CACHE.with(|cache| cache.borrow().in_flight.get(request).cloned());
+        if let Some(future) = in_flight {
+            return future;
+        };
+        let future = self
+            .transport
+            .resource(&request.path)
+            .map({
+                let request = request.to_owned();
+                move |result| {
+                    RESOURCE_CACHE.with(|cache| {
+                        let mut cache = cache.borrow_mut();
+                        cache.in_flight.remove(&request);
+                        match result {
+                            Ok(response) => {
+                                cache.insert(request, response.to_owned(), E::now());
+                                Ok(response)
+                            }
+                            Err(error) => cache.get_on_error(&request, E::now()).ok_or(error),
+                        }
+                    })
+                }
+            })
+            .boxed_local()
+            .shared();
+        RESOURCE_CACHE.with(|cache| {
+            cache
+                .borrow_mut()
+                .in_flight
+                .insert(request.to_owned(), future.to_owned());
+        });
+        future
+    }
+}
+
+impl<E: Env + 'static> AddonTransport for AddonCachedTransport<E> {
+    fn resource(&self, path: &ResourcePath) -> TryEnvFuture<ResourceResponse> {
+        let request = ResourceRequest::new(self.transport_url.to_owned(), path.to_owned());
+        let lookup = RESOURCE_CACHE.with(|cache| cache.borrow_mut().get(&request, E::now()));
+        match lookup {
+            CacheLookup::Fresh(response) => future::ok(response).boxed_local(),
+            CacheLookup::Stale(response) => {
+                E::exec(self.fetch(&request).map(|_| ()));
+                future::ok(response).boxed_local()
+            }
+            CacheLookup::Miss => self.fetch(&request).boxed_local(),
+        }
+    }
+    fn manifest(&self) -> TryEnvFuture<Manifest> {
+        self.transport.manifest()
+    }
+}
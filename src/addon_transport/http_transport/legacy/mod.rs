@@ -65,23 +65,33 @@ pub struct SubtitlesResult {
 
 impl From<Vec<MetaItemPreview>> for ResourceResponse {
     fn from(metas: Vec<MetaItemPreview>) -> Self {
-        ResourceResponse::Metas { metas }
+        ResourceResponse::Metas {
+            metas,
+            cache_hints: Default::default(),
+        }
     }
 }
 impl From<MetaItem> for ResourceResponse {
     fn from(meta: MetaItem) -> Self {
-        ResourceResponse::Meta { meta }
+        ResourceResponse::Meta {
+            meta,
+            cache_hints: Default::default(),
+        }
     }
 }
 impl From<Vec<Stream>> for ResourceResponse {
     fn from(streams: Vec<Stream>) -> Self {
-        ResourceResponse::Streams { streams }
+        ResourceResponse::Streams {
+            streams,
+            cache_hints: Default::default(),
+        }
     }
 }
 impl From<SubtitlesResult> for ResourceResponse {
     fn from(subtitles_result: SubtitlesResult) -> Self {
         ResourceResponse::Subtitles {
             subtitles: subtitles_result.all,
+            cache_hints: Default::default(),
         }
     }
 }
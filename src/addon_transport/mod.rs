@@ -4,5 +4,8 @@ pub use http_transport::*;
 mod addon_transport;
 pub use addon_transport::*;
 
+mod cached_transport;
+pub use cached_transport::*;
+
 mod unsupported_transport;
 pub use unsupported_transport::*;
@@ -1,4 +1,5 @@
 use crate::types::addon::Descriptor;
+use chrono::Duration;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use url::Url;
@@ -7,6 +8,10 @@ pub const SCHEMA_VERSION_STORAGE_KEY: &str = "schema_version";
 pub const PROFILE_STORAGE_KEY: &str = "profile";
 pub const LIBRARY_STORAGE_KEY: &str = "library";
 pub const LIBRARY_RECENT_STORAGE_KEY: &str = "library_recent";
+pub const OUTBOX_STORAGE_KEY: &str = "outbox";
+pub const SEARCH_HISTORY_STORAGE_KEY: &str = "search_history";
+pub const STREAMS_STORAGE_KEY: &str = "streams";
+pub const SUBTITLES_STORAGE_KEY: &str = "subtitles";
 pub const LIBRARY_COLLECTION_NAME: &str = "libraryItem";
 pub const SKIP_EXTRA_NAME: &str = "skip";
 pub const SEARCH_EXTRA_NAME: &str = "search";
@@ -18,13 +23,26 @@ pub const ADDON_LEGACY_PATH: &str = "/stremio/v1";
 pub const CATALOG_PAGE_SIZE: usize = 100;
 pub const CATALOG_PREVIEW_SIZE: usize = 10;
 pub const LIBRARY_RECENT_COUNT: usize = 200;
+pub const LIBRARY_SYNC_BATCH_SIZE: usize = 100;
+pub const SEARCH_HISTORY_MAX_COUNT: usize = 50;
+pub const SEARCH_HISTORY_RECENT_COUNT: usize = 10;
+pub const STREAMS_MAX_COUNT: usize = 500;
+pub const NOTIFICATION_ITEMS_COUNT: usize = 100;
+pub const ADDON_RESOURCE_CACHE_SIZE: usize = 200;
 pub const WATCHED_THRESHOLD_COEF: f64 = 0.7;
 pub const SCHEMA_VERSION: u32 = 2;
+pub const LIBRARY_EXPORT_VERSION: u32 = 1;
 
 lazy_static! {
     pub static ref API_URL: Url = Url::parse("https://api.strem.io").expect("API_URL parse failed");
     pub static ref STREAMING_SERVER_URL: Url =
         Url::parse("http://127.0.0.1:11470").expect("STREAMING_SERVER_URL parse failed");
+    pub static ref ADDON_RESOURCE_CACHE_TTL: Duration = Duration::minutes(10);
+    pub static ref SEARCH_DEBOUNCE_DELAY: Duration = Duration::milliseconds(300);
+    pub static ref OUTBOX_RETRY_BASE_DELAY: Duration = Duration::seconds(5);
+    pub static ref NOTIFICATIONS_REFRESH_INTERVAL: Duration = Duration::hours(1);
+    pub static ref OUTBOX_RETRY_MAX_DELAY: Duration = Duration::minutes(30);
+    pub static ref LIBRARY_PURGE_AGE: Duration = Duration::days(30);
     pub static ref OFFICIAL_ADDONS: Vec<Descriptor> =
         serde_json::from_slice(stremio_official_addons::ADDONS)
             .expect("OFFICIAL_ADDONS parse failed");
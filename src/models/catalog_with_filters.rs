@@ -1,10 +1,11 @@
 use crate::constants::{CATALOG_PAGE_SIZE, SKIP_EXTRA_NAME, TYPE_PRIORITIES};
 use crate::models::common::{
-    compare_with_priorities, eq_update, resource_update_with_vector_content, Loadable,
-    ResourceAction, ResourceLoadable,
+    compare_with_priorities, eq_update, resource_update_with_vector_content,
+    resources_update_with_vector_content, Loadable, ResourceAction, ResourceLoadable,
+    ResourcesAction,
 };
 use crate::models::ctx::Ctx;
-use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::msg::{Action, ActionCatalogWithFilters, ActionLoad, Internal, Msg};
 use crate::runtime::{Effects, Env, UpdateWithCtx};
 use crate::types::addon::{
     DescriptorPreview, ExtraExt, Manifest, ManifestCatalog, ResourcePath, ResourceRequest,
@@ -15,7 +16,8 @@ use crate::types::resource::MetaItemPreview;
 use boolinator::Boolinator;
 use derivative::Derivative;
 use itertools::Itertools;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashSet;
 use std::convert::TryFrom;
 
 #[derive(PartialEq)]
@@ -29,6 +31,7 @@ pub trait CatalogResourceAdapter {
     fn catalogs(manifest: &Manifest) -> &[ManifestCatalog];
     fn selectable_priority() -> SelectablePriority;
     fn page_size() -> Option<usize>;
+    fn id(&self) -> &str;
 }
 
 impl CatalogResourceAdapter for MetaItemPreview {
@@ -44,6 +47,9 @@ impl CatalogResourceAdapter for MetaItemPreview {
     fn page_size() -> Option<usize> {
         Some(CATALOG_PAGE_SIZE)
     }
+    fn id(&self) -> &str {
+        &self.id
+    }
 }
 
 impl CatalogResourceAdapter for DescriptorPreview {
@@ -59,6 +65,9 @@ impl CatalogResourceAdapter for DescriptorPreview {
     fn page_size() -> Option<usize> {
         None
     }
+    fn id(&self) -> &str {
+        self.transport_url.as_str()
+    }
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -113,17 +122,21 @@ pub struct Selectable {
 
 #[derive(Derivative, Serialize)]
 #[derivative(Default(bound = ""))]
+#[serde(rename_all = "camelCase", bound(serialize = "T: Serialize"))]
 pub struct CatalogWithFilters<T> {
     pub selected: Option<Selected>,
     pub selectable: Selectable,
-    pub catalog: Option<ResourceLoadable<Vec<T>>>,
+    /// Loaded pages, serialized as a single catalog with the items of all ready pages
+    #[serde(serialize_with = "serialize_catalog")]
+    pub catalog: Vec<ResourceLoadable<Vec<T>>>,
+    pub has_next_page: bool,
 }
 
 impl<T: CatalogResourceAdapter> CatalogWithFilters<T> {
     pub fn new(profile: &Profile) -> (Self, Effects) {
-        let catalog = None;
+        let catalog = vec![];
         let mut selectable = Selectable::default();
-        let effects = selectable_update::<T>(&mut selectable, &catalog, &profile);
+        let effects = selectable_update::<T>(&mut selectable, &catalog, false, &profile);
         (
             Self {
                 selectable,
@@ -145,53 +158,163 @@ where
         match msg {
             Msg::Action(Action::Load(ActionLoad::CatalogWithFilters(selected))) => {
                 let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
-                let catalog_effects = resource_update_with_vector_content::<E, _>(
-                    &mut self.catalog,
-                    ResourceAction::ResourceRequested {
-                        request: &selected.request,
-                    },
+                let catalog_effects = match self.catalog.first() {
+                    Some(page) if page.request == selected.request => Effects::none().unchanged(),
+                    _ => {
+                        self.catalog = vec![];
+                        self.has_next_page = false;
+                        page_update::<E, _>(&mut self.catalog, &selected.request)
+                    }
+                };
+                let selectable_effects = selectable_update(
+                    &mut self.selectable,
+                    &self.catalog,
+                    self.has_next_page,
+                    &ctx.profile,
                 );
-                let selectable_effects =
-                    selectable_update(&mut self.selectable, &self.catalog, &ctx.profile);
                 selected_effects
                     .join(catalog_effects)
                     .join(selectable_effects)
             }
+            Msg::Action(Action::CatalogWithFilters(ActionCatalogWithFilters::LoadNextPage)) => {
+                match self.selectable.next_page.as_ref() {
+                    Some(next_page) => {
+                        let catalog_effects =
+                            page_update::<E, _>(&mut self.catalog, &next_page.request);
+                        self.has_next_page = false;
+                        let selectable_effects = selectable_update(
+                            &mut self.selectable,
+                            &self.catalog,
+                            self.has_next_page,
+                            &ctx.profile,
+                        );
+                        catalog_effects.join(selectable_effects)
+                    }
+                    _ => Effects::none().unchanged(),
+                }
+            }
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
-                let catalog_effects = eq_update(&mut self.catalog, None);
-                let selectable_effects =
-                    selectable_update(&mut self.selectable, &self.catalog, &ctx.profile);
+                let catalog_effects = eq_update(&mut self.catalog, vec![]);
+                self.has_next_page = false;
+                let selectable_effects = selectable_update(
+                    &mut self.selectable,
+                    &self.catalog,
+                    self.has_next_page,
+                    &ctx.profile,
+                );
                 selected_effects
                     .join(catalog_effects)
                     .join(selectable_effects)
             }
             Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
-                let catalog_effects = resource_update_with_vector_content::<E, _>(
+                let catalog_effects = resources_update_with_vector_content::<E, _>(
                     &mut self.catalog,
-                    ResourceAction::ResourceRequestResult {
+                    ResourcesAction::ResourceRequestResult {
                         request,
                         result,
                         limit: &T::page_size(),
                     },
                 );
-                let selectable_effects =
-                    selectable_update(&mut self.selectable, &self.catalog, &ctx.profile);
+                if catalog_effects.has_changed {
+                    self.has_next_page = match self.catalog.last() {
+                        Some(ResourceLoadable {
+                            request: last_page_request,
+                            content: Loadable::Ready(content),
+                        }) if last_page_request == request => T::page_size() == Some(content.len()),
+                        _ => self.has_next_page,
+                    };
+                    dedup_pages(&mut self.catalog, request);
+                };
+                let selectable_effects = selectable_update(
+                    &mut self.selectable,
+                    &self.catalog,
+                    self.has_next_page,
+                    &ctx.profile,
+                );
                 catalog_effects.join(selectable_effects)
             }
-            Msg::Internal(Internal::ProfileChanged) => {
-                selectable_update(&mut self.selectable, &self.catalog, &ctx.profile)
-            }
+            Msg::Internal(Internal::ProfileChanged) => selectable_update(
+                &mut self.selectable,
+                &self.catalog,
+                self.has_next_page,
+                &ctx.profile,
+            ),
             _ => Effects::none().unchanged(),
         }
     }
 }
 
+fn page_update<E, T>(
+    catalog: &mut Vec<ResourceLoadable<Vec<T>>>,
+    request: &ResourceRequest,
+) -> Effects
+where
+    E: Env + 'static,
+    Vec<T>: TryFrom<ResourceResponse, Error = &'static str>,
+{
+    let mut page = None;
+    let page_effects = resource_update_with_vector_content::<E, _>(
+        &mut page,
+        ResourceAction::ResourceRequested { request },
+    );
+    catalog.extend(page);
+    page_effects
+}
+
+fn dedup_pages<T: CatalogResourceAdapter>(
+    catalog: &mut [ResourceLoadable<Vec<T>>],
+    request: &ResourceRequest,
+) {
+    if let Some(position) = catalog.iter().position(|page| page.request == *request) {
+        let (prev_pages, next_pages) = catalog.split_at_mut(position);
+        let prev_ids = prev_pages
+            .iter()
+            .filter_map(|page| page.content.as_ref().ready())
+            .flatten()
+            .map(|item| item.id())
+            .collect::<HashSet<_>>();
+        if let Loadable::Ready(content) = &mut next_pages[0].content {
+            content.retain(|item| !prev_ids.contains(item.id()));
+        };
+    };
+}
+
+fn serialize_catalog<T, S>(
+    catalog: &[ResourceLoadable<Vec<T>>],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Serialize,
+    S: Serializer,
+{
+    catalog
+        .first()
+        .map(|first_page| ResourceLoadable {
+            request: first_page.request.to_owned(),
+            content: match &first_page.content {
+                Loadable::Ready(_) => Loadable::Ready(
+                    catalog
+                        .iter()
+                        .filter_map(|page| page.content.as_ref().ready())
+                        .flatten()
+                        .collect::<Vec<_>>(),
+                ),
+                Loadable::Loading => Loadable::Loading,
+                Loadable::Err(error) => Loadable::Err(error.to_owned()),
+            },
+        })
+        .serialize(serializer)
+}
+
 fn selectable_update<T: CatalogResourceAdapter>(
     selectable: &mut Selectable,
-    catalog: &Option<ResourceLoadable<Vec<T>>>,
+    catalog: &[ResourceLoadable<Vec<T>>],
+    has_next_page: bool,
     profile: &Profile,
 ) -> Effects {
+    let last_page = catalog.last();
+    let catalog = catalog.first();
     let selectable_catalogs = profile
         .addons
         .iter()
@@ -210,7 +333,6 @@ fn selectable_update<T: CatalogResourceAdapter>(
                         .unwrap_or(&manifest_catalog.id)
                         .to_owned(),
                     selected: catalog
-                        .as_ref()
                         .map(|catalog| {
                             catalog.request.base == addon.transport_url
                                 && catalog.request.path.id == manifest_catalog.id
@@ -246,7 +368,6 @@ fn selectable_update<T: CatalogResourceAdapter>(
         .map(|request| SelectableType {
             r#type: request.path.r#type.to_owned(),
             selected: catalog
-                .as_ref()
                 .map(|catalog| catalog.request.path.r#type == request.path.r#type)
                 .unwrap_or_default(),
             request,
@@ -384,8 +505,14 @@ fn selectable_update<T: CatalogResourceAdapter>(
                                 },
                             },
                         });
-                        let next_page = match &catalog.content {
-                            Loadable::Ready(content) if content.len() == page_size => {
+                        let next_page = match last_page {
+                            Some(last_page) if has_next_page && last_page.content.is_ready() => {
+                                let skip = last_page
+                                    .request
+                                    .path
+                                    .get_extra_first_value(SKIP_EXTRA_NAME)
+                                    .and_then(|value| value.parse::<u32>().ok())
+                                    .unwrap_or(0);
                                 Some(SelectablePage {
                                     request: ResourceRequest {
                                         base: catalog.request.base.to_owned(),
@@ -4,6 +4,7 @@ use crate::runtime::{Effects, Env, EnvError};
 use crate::types::addon::{AggrRequest, Descriptor, ResourceRequest, ResourceResponse};
 use futures::FutureExt;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt;
 
@@ -142,14 +143,21 @@ where
                 .iter()
                 .ne(resources.iter().map(|resource| &resource.request))
             {
+                let mut prev_resources = resources
+                    .drain(..)
+                    .map(|resource| (resource.request.to_owned(), resource))
+                    .collect::<HashMap<_, _>>();
                 let (next_resources, effects) = requests
                     .iter()
                     .cloned()
                     .map(|request| {
                         (
-                            ResourceLoadable {
-                                request: request.to_owned(),
-                                content: Loadable::Loading,
+                            match prev_resources.remove(&request) {
+                                Some(resource) if resource.content.is_ready() => resource,
+                                _ => ResourceLoadable {
+                                    request: request.to_owned(),
+                                    content: Loadable::Loading,
+                                },
                             },
                             E::addon_transport(&request.base)
                                 .resource(&request.path)
@@ -1,13 +1,20 @@
 use crate::constants::LIBRARY_COLLECTION_NAME;
-use crate::models::ctx::{update_library, update_profile, CtxError};
+use crate::models::ctx::{
+    plan_sync_with_api, update_library, update_outbox, update_profile, update_search_history,
+    update_streams, update_subtitles, CtxError,
+};
 use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
 use crate::runtime::{Effect, Effects, Env, Update};
 use crate::types::api::{
     fetch_api, APIRequest, APIResult, AuthRequest, AuthResponse, CollectionResponse,
-    DatastoreCommand, DatastoreRequest, SuccessResponse,
+    DatastoreCommand, DatastoreRequest, SettingsResponse, SuccessResponse,
 };
 use crate::types::library::LibraryBucket;
+use crate::types::outbox::OutboxBucket;
 use crate::types::profile::{Auth, AuthKey, Profile};
+use crate::types::search_history::SearchHistoryBucket;
+use crate::types::streams::StreamsBucket;
+use crate::types::subtitles::SubtitlesBucket;
 use derivative::Derivative;
 use enclose::enclose;
 use futures::{future, FutureExt, TryFutureExt};
@@ -23,12 +30,17 @@ pub enum CtxStatus {
 #[derivative(Default)]
 pub struct Ctx {
     pub profile: Profile,
-    // TODO StreamsBucket
-    // TODO SubtitlesBucket
-    // TODO SearchesBucket
     #[serde(skip)]
     pub library: LibraryBucket,
     #[serde(skip)]
+    pub outbox: OutboxBucket,
+    #[serde(skip)]
+    pub search_history: SearchHistoryBucket,
+    #[serde(skip)]
+    pub streams: StreamsBucket,
+    #[serde(skip)]
+    pub subtitles: SubtitlesBucket,
+    #[serde(skip)]
     #[derivative(Default(value = "CtxStatus::Ready"))]
     pub status: CtxStatus,
 }
@@ -41,6 +53,31 @@ impl Ctx {
             ..Self::default()
         }
     }
+    pub fn with_outbox(self, outbox: OutboxBucket) -> Self {
+        Self { outbox, ..self }
+    }
+    pub fn with_search_history(self, search_history: SearchHistoryBucket) -> Self {
+        Self {
+            search_history,
+            ..self
+        }
+    }
+    pub fn with_streams(self, streams: StreamsBucket) -> Self {
+        Self { streams, ..self }
+    }
+    pub fn with_subtitles(self, subtitles: SubtitlesBucket) -> Self {
+        Self { subtitles, ..self }
+    }
+    // Effects to pass to the runtime once the ctx is restored from storage.
+    // The library of a logged in user is synced with the API right away.
+    pub fn storage_loaded_effects<E: Env + 'static>(&self) -> Effects {
+        match self.profile.auth_key() {
+            Some(auth_key) => {
+                Effects::one(plan_sync_with_api::<E>(&self.library, auth_key)).unchanged()
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
 }
 
 impl<E: Env + 'static> Update<E> for Ctx {
@@ -63,12 +100,23 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     &self.status,
                     &msg,
                 );
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let search_history_effects =
+                    update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
+                let subtitles_effects =
+                    update_subtitles::<E>(&mut self.subtitles, &self.status, msg);
                 self.status = CtxStatus::Ready;
                 Effects::msg(Msg::Event(Event::UserLoggedOut { uid }))
                     .unchanged()
                     .join(session_effects)
                     .join(profile_effects)
                     .join(library_effects)
+                    .join(outbox_effects)
+                    .join(search_history_effects)
+                    .join(streams_effects)
+                    .join(subtitles_effects)
             }
             Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => {
                 let profile_effects = update_profile::<E>(&mut self.profile, &self.status, msg);
@@ -78,6 +126,13 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     &self.status,
                     msg,
                 );
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let search_history_effects =
+                    update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
+                let subtitles_effects =
+                    update_subtitles::<E>(&mut self.subtitles, &self.status, msg);
                 let ctx_effects = match &self.status {
                     CtxStatus::Loading(loading_auth_request)
                         if loading_auth_request == auth_request =>
@@ -99,7 +154,13 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     }
                     _ => Effects::none().unchanged(),
                 };
-                profile_effects.join(library_effects).join(ctx_effects)
+                profile_effects
+                    .join(library_effects)
+                    .join(outbox_effects)
+                    .join(search_history_effects)
+                    .join(streams_effects)
+                    .join(subtitles_effects)
+                    .join(ctx_effects)
             }
             _ => {
                 let profile_effects = update_profile::<E>(&mut self.profile, &self.status, &msg);
@@ -109,7 +170,19 @@ impl<E: Env + 'static> Update<E> for Ctx {
                     &self.status,
                     &msg,
                 );
-                profile_effects.join(library_effects)
+                let outbox_effects =
+                    update_outbox::<E>(&mut self.outbox, &self.profile, &self.status, msg);
+                let search_history_effects =
+                    update_search_history::<E>(&mut self.search_history, &self.status, msg);
+                let streams_effects = update_streams::<E>(&mut self.streams, &self.status, msg);
+                let subtitles_effects =
+                    update_subtitles::<E>(&mut self.subtitles, &self.status, msg);
+                profile_effects
+                    .join(library_effects)
+                    .join(outbox_effects)
+                    .join(search_history_effects)
+                    .join(streams_effects)
+                    .join(subtitles_effects)
             }
         }
     }
@@ -127,7 +200,7 @@ fn authenticate<E: Env + 'static>(auth_request: &AuthRequest) -> Effect {
         })
         .map_ok(|AuthResponse { key, user }| Auth { key, user })
         .and_then(|auth| {
-            future::try_join(
+            let addons_and_library_items = future::try_join(
                 fetch_api::<E, _, _>(&APIRequest::AddonCollectionGet {
                     auth_key: auth.key.to_owned(),
                     update: true,
@@ -151,8 +224,18 @@ fn authenticate<E: Env + 'static>(auth_request: &AuthRequest) -> Effect {
                     APIResult::Ok { result } => future::ok(result),
                     APIResult::Err { error } => future::err(CtxError::from(error)),
                 }),
-            )
-            .map_ok(move |(addons, library_items)| (auth, addons, library_items))
+            );
+            // settings are optional, so failing to fetch them must not fail the login
+            let settings = fetch_api::<E, _, Option<SettingsResponse>>(&APIRequest::SettingsGet {
+                auth_key: auth.key.to_owned(),
+            })
+            .map(|result| match result {
+                Ok(APIResult::Ok { result }) => result,
+                _ => None,
+            });
+            future::join(addons_and_library_items, settings).map(move |(result, settings)| {
+                result.map(|(addons, library_items)| (auth, addons, library_items, settings))
+            })
         })
         .map(enclose!((auth_request) move |result| {
             Msg::Internal(Internal::CtxAuthResult(auth_request, result))
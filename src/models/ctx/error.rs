@@ -37,6 +37,7 @@ pub enum OtherError {
     AddonNotInstalled,
     AddonIsProtected,
     AddonConfigurationRequired,
+    LibraryExportVersionNotSupported,
 }
 
 impl OtherError {
@@ -48,6 +49,9 @@ impl OtherError {
             OtherError::AddonNotInstalled => "Addon is not installed".to_owned(),
             OtherError::AddonIsProtected => "Addon is protected".to_owned(),
             OtherError::AddonConfigurationRequired => "Addon requires configuration".to_owned(),
+            OtherError::LibraryExportVersionNotSupported => {
+                "Library export version is not supported".to_owned()
+            }
         }
     }
     pub fn code(&self) -> u64 {
@@ -58,6 +62,7 @@ impl OtherError {
             OtherError::AddonNotInstalled => 4,
             OtherError::AddonIsProtected => 5,
             OtherError::AddonConfigurationRequired => 6,
+            OtherError::LibraryExportVersionNotSupported => 7,
         }
     }
 }
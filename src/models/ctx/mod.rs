@@ -1,9 +1,21 @@
 mod update_library;
 use update_library::*;
 
+mod update_outbox;
+use update_outbox::*;
+
 mod update_profile;
 use update_profile::*;
 
+mod update_search_history;
+use update_search_history::*;
+
+mod update_streams;
+use update_streams::*;
+
+mod update_subtitles;
+use update_subtitles::*;
+
 mod error;
 pub use error::*;
 
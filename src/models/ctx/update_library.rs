@@ -1,18 +1,22 @@
 use crate::constants::{
-    LIBRARY_COLLECTION_NAME, LIBRARY_RECENT_COUNT, LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY,
+    LIBRARY_COLLECTION_NAME, LIBRARY_EXPORT_VERSION, LIBRARY_PURGE_AGE, LIBRARY_RECENT_COUNT,
+    LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY, LIBRARY_SYNC_BATCH_SIZE,
 };
 use crate::models::ctx::{CtxError, CtxStatus, OtherError};
-use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, LibrarySyncPlan, Msg};
 use crate::runtime::{Effect, Effects, Env};
 use crate::types::api::{
     fetch_api, APIResult, DatastoreCommand, DatastoreRequest, LibraryItemModified, SuccessResponse,
 };
 use crate::types::library::{
-    LibraryBucket, LibraryBucketRef, LibraryItem, LibraryItemBehaviorHints, LibraryItemState,
+    LibraryBucket, LibraryBucketRef, LibraryExport, LibraryItem, LibraryItemBehaviorHints,
+    LibraryItemState,
 };
 use crate::types::profile::AuthKey;
+use crate::types::resource::MetaItem;
 use futures::future::Either;
 use futures::{future, FutureExt, TryFutureExt};
+use std::cmp;
 use std::collections::HashMap;
 
 pub fn update_library<E: Env + 'static>(
@@ -53,6 +57,11 @@ pub fn update_library<E: Env + 'static>(
                     library_item.ctime = Some(ctime.to_owned());
                 };
             };
+            // videos released before the item was added are not notifications,
+            // the preview carries no videos so the newest release is unknown here
+            if library_item.state.last_vid_released.is_none() {
+                library_item.state.last_vid_released = Some(E::now());
+            };
             Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
                 .join(Effects::msg(Msg::Event(Event::LibraryItemAdded {
                     id: meta_preview.id.to_owned(),
@@ -91,6 +100,178 @@ pub fn update_library<E: Env + 'static>(
             }))
             .unchanged(),
         },
+        Msg::Action(Action::Ctx(ActionCtx::RestoreLibraryItem(id))) => match library.items.get(id) {
+            Some(library_item) if library_item.removed => {
+                let mut library_item = library_item.to_owned();
+                library_item.removed = false;
+                Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                    .join(Effects::msg(Msg::Event(Event::LibraryItemRestored {
+                        id: id.to_owned(),
+                    })))
+                    .unchanged()
+            }
+            _ => Effects::msg(Msg::Event(Event::Error {
+                error: CtxError::from(OtherError::LibraryItemNotFound),
+                source: Box::new(Event::LibraryItemRestored { id: id.to_owned() }),
+            }))
+            .unchanged(),
+        },
+        Msg::Action(Action::Ctx(ActionCtx::PurgeLibraryItems)) => {
+            let purge_before = E::now() - *LIBRARY_PURGE_AGE;
+            let ids = library
+                .items
+                .values()
+                .filter(|library_item| {
+                    library_item.removed
+                        && !library_item.should_sync()
+                        && library_item.mtime < purge_before
+                })
+                .map(|library_item| library_item.id.to_owned())
+                .collect::<Vec<_>>();
+            if !ids.is_empty() {
+                library.items.retain(|id, _| !ids.contains(id));
+                Effects::msg(Msg::Event(Event::LibraryItemsPurged { ids }))
+                    .join(Effects::msg(Msg::Internal(Internal::LibraryChanged(false))))
+            } else {
+                Effects::msg(Msg::Event(Event::LibraryItemsPurged { ids })).unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ExportLibrary)) => {
+            let mut items = library.items.values().cloned().collect::<Vec<_>>();
+            items.sort_by(|a, b| b.mtime.cmp(&a.mtime).then_with(|| a.id.cmp(&b.id)));
+            Effects::msg(Msg::Event(Event::LibraryExported {
+                export: LibraryExport::new(items),
+            }))
+            .unchanged()
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ImportLibrary(export)))
+            if export.version == LIBRARY_EXPORT_VERSION =>
+        {
+            let mut items = HashMap::<&str, &LibraryItem>::new();
+            for item in export.items.iter().filter(|item| !item.id.is_empty()) {
+                let current_item = items
+                    .get(item.id.as_str())
+                    .copied()
+                    .or_else(|| library.items.get(&item.id));
+                if !matches!(current_item, Some(current_item) if current_item.mtime >= item.mtime)
+                {
+                    items.insert(&item.id, item);
+                };
+            }
+            let items = items.into_values().cloned().collect::<Vec<_>>();
+            let updated = items
+                .iter()
+                .filter(|item| library.items.contains_key(&item.id))
+                .count();
+            let imported_effects = Effects::msg(Msg::Event(Event::LibraryImported {
+                added: items.len() - updated,
+                updated,
+                skipped: export.items.len() - items.len(),
+            }))
+            .unchanged();
+            if !items.is_empty() {
+                let push_to_api_effects = match auth_key {
+                    Some(auth_key) => {
+                        Effects::one(push_items_to_api::<E>(items.to_owned(), auth_key))
+                            .unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
+                push_to_api_effects
+                    .join(Effects::one(update_and_push_items_to_storage::<E>(
+                        library, items,
+                    )))
+                    .join(Effects::msg(Msg::Internal(Internal::LibraryChanged(true))))
+                    .join(imported_effects)
+            } else {
+                imported_effects
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ImportLibrary(_))) => {
+            Effects::msg(Msg::Event(Event::Error {
+                error: CtxError::from(OtherError::LibraryExportVersionNotSupported),
+                source: Box::new(Event::LibraryImported {
+                    added: 0,
+                    updated: 0,
+                    skipped: 0,
+                }),
+            }))
+            .unchanged()
+        }
+        Msg::Action(Action::Ctx(ActionCtx::SetLibraryItemNotifications { id, no_notif }))
+        | Msg::Internal(Internal::SetLibraryItemNotifications { id, no_notif }) => {
+            match library.items.get(id) {
+                Some(library_item) => {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.no_notif = *no_notif;
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .join(Effects::msg(Msg::Event(
+                            Event::LibraryItemNotificationsToggled {
+                                id: id.to_owned(),
+                                no_notif: *no_notif,
+                            },
+                        )))
+                        .unchanged()
+                }
+                _ => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::LibraryItemNotFound),
+                    source: Box::new(Event::LibraryItemNotificationsToggled {
+                        id: id.to_owned(),
+                        no_notif: *no_notif,
+                    }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MarkAsWatched { id, watched })) => {
+            match library.items.get(id) {
+                Some(library_item) => {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.mark_as_watched(*watched, E::now());
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .join(Effects::msg(Msg::Event(Event::LibraryItemMarkedAsWatched {
+                            id: id.to_owned(),
+                            watched: *watched,
+                        })))
+                        .unchanged()
+                }
+                _ => Effects::msg(Msg::Event(Event::Error {
+                    error: CtxError::from(OtherError::LibraryItemNotFound),
+                    source: Box::new(Event::LibraryItemMarkedAsWatched {
+                        id: id.to_owned(),
+                        watched: *watched,
+                    }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::MarkVideoAsWatched {
+            meta_item,
+            video_id,
+            watched,
+        })) => mark_videos_as_watched::<E>(
+            library,
+            meta_item,
+            vec![video_id.as_str()],
+            *watched,
+        ),
+        Msg::Action(Action::Ctx(ActionCtx::MarkSeasonAsWatched {
+            meta_item,
+            season,
+            watched,
+        })) => mark_videos_as_watched::<E>(
+            library,
+            meta_item,
+            meta_item
+                .videos
+                .iter()
+                .filter(|video| {
+                    matches!(&video.series_info, Some(series_info) if series_info.season == *season)
+                })
+                .map(|video| video.id.as_str())
+                .collect(),
+            *watched,
+        ),
         Msg::Action(Action::Ctx(ActionCtx::SyncLibraryWithAPI)) => match auth_key {
             Some(auth_key) => Effects::one(plan_sync_with_api::<E>(library, auth_key)).unchanged(),
             _ => Effects::msg(Msg::Event(Event::Error {
@@ -124,17 +305,30 @@ pub fn update_library<E: Env + 'static>(
             Effects::one(push_library_to_storage::<E>(library)).unchanged()
         }
         Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
-            (CtxStatus::Loading(loading_auth_request), Ok((auth, _, library_items)))
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, _, library_items, _)))
                 if loading_auth_request == auth_request =>
             {
-                let next_library =
+                let mut next_library =
                     LibraryBucket::new(Some(auth.user.id.to_owned()), library_items.to_owned());
-                if *library != next_library {
+                // items added while logged out are kept and pushed by the following sync
+                if library.uid.is_none() {
+                    next_library.merge_items(
+                        library
+                            .items
+                            .values()
+                            .filter(|library_item| library_item.should_sync())
+                            .cloned()
+                            .collect(),
+                    );
+                };
+                let library_effects = if *library != next_library {
                     *library = next_library;
                     Effects::msg(Msg::Internal(Internal::LibraryChanged(false)))
                 } else {
                     Effects::none().unchanged()
-                }
+                };
+                library_effects
+                    .join(Effects::one(plan_sync_with_api::<E>(library, &auth.key)).unchanged())
             }
             _ => Effects::none().unchanged(),
         },
@@ -145,35 +339,15 @@ pub fn update_library<E: Env + 'static>(
             },
             result,
         )) if Some(loading_auth_key) == auth_key => match result {
-            Ok((pull_ids, push_ids)) => {
-                let push_items = library
-                    .items
-                    .iter()
-                    .filter(move |(id, _)| push_ids.iter().any(|push_id| push_id == *id))
-                    .map(|(_, item)| item)
-                    .cloned()
-                    .collect::<Vec<_>>();
-                let push_items_to_api_effects = if push_items.is_empty() {
-                    Effects::none().unchanged()
-                } else {
-                    Effects::one(push_items_to_api::<E>(push_items, loading_auth_key)).unchanged()
-                };
-                let pull_items_from_api_effects = if pull_ids.is_empty() {
-                    Effects::none().unchanged()
-                } else {
-                    Effects::one(pull_items_from_api::<E>(
-                        pull_ids.to_owned(),
-                        loading_auth_key,
-                    ))
-                    .unchanged()
-                };
-                Effects::msg(Msg::Event(Event::LibrarySyncWithAPIPlanned {
-                    plan: (pull_ids.to_owned(), push_ids.to_owned()),
-                }))
-                .join(push_items_to_api_effects)
-                .join(pull_items_from_api_effects)
-                .unchanged()
-            }
+            Ok(plan) => Effects::msg(Msg::Event(Event::LibrarySyncWithAPIPlanned {
+                plan: (plan.pull_ids.to_owned(), plan.push_ids.to_owned()),
+            }))
+            .unchanged()
+            .join(sync_next_batch_with_api::<E>(
+                library,
+                plan.to_owned(),
+                loading_auth_key,
+            )),
             Err(error) => Effects::msg(Msg::Event(Event::Error {
                 error: error.to_owned(),
                 source: Box::new(Event::LibrarySyncWithAPIPlanned {
@@ -182,12 +356,65 @@ pub fn update_library<E: Env + 'static>(
             }))
             .unchanged(),
         },
+        Msg::Internal(Internal::LibraryPushResult(
+            DatastoreRequest {
+                command: DatastoreCommand::Put { changes },
+                ..
+            },
+            result,
+        )) => {
+            let ids = changes
+                .iter()
+                .map(|item| &item.id)
+                .cloned()
+                .collect::<Vec<_>>();
+            match result {
+                Ok(_) => {
+                    Effects::msg(Msg::Event(Event::LibraryItemsPushedToAPI { ids })).unchanged()
+                }
+                Err(error) => Effects::msg(Msg::Event(Event::Error {
+                    error: error.to_owned(),
+                    source: Box::new(Event::LibraryItemsPushedToAPI { ids }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Internal(Internal::LibrarySyncPushResult(
+            DatastoreRequest {
+                auth_key: loading_auth_key,
+                command: DatastoreCommand::Put { changes },
+                ..
+            },
+            plan,
+            result,
+        )) if Some(loading_auth_key) == auth_key => {
+            let ids = changes
+                .iter()
+                .map(|item| &item.id)
+                .cloned()
+                .collect::<Vec<_>>();
+            match result {
+                Ok(_) => Effects::msg(Msg::Event(Event::LibraryItemsPushedToAPI { ids }))
+                    .unchanged()
+                    .join(sync_next_batch_with_api::<E>(
+                        library,
+                        plan.to_owned(),
+                        loading_auth_key,
+                    )),
+                Err(error) => Effects::msg(Msg::Event(Event::Error {
+                    error: error.to_owned(),
+                    source: Box::new(Event::LibraryItemsPushedToAPI { ids }),
+                }))
+                .unchanged(),
+            }
+        }
         Msg::Internal(Internal::LibraryPullResult(
             DatastoreRequest {
                 auth_key: loading_auth_key,
                 command: DatastoreCommand::Get { ids, .. },
                 ..
             },
+            plan,
             result,
         )) if Some(loading_auth_key) == auth_key => match result {
             Ok(items) => Effects::msg(Msg::Event(Event::LibraryItemsPulledFromAPI {
@@ -197,7 +424,12 @@ pub fn update_library<E: Env + 'static>(
                 library,
                 items.to_owned(),
             )))
-            .join(Effects::msg(Msg::Internal(Internal::LibraryChanged(true)))),
+            .join(Effects::msg(Msg::Internal(Internal::LibraryChanged(true))))
+            .join(sync_next_batch_with_api::<E>(
+                library,
+                plan.to_owned(),
+                loading_auth_key,
+            )),
             Err(error) => Effects::msg(Msg::Event(Event::Error {
                 error: error.to_owned(),
                 source: Box::new(Event::LibraryItemsPulledFromAPI {
@@ -210,6 +442,41 @@ pub fn update_library<E: Env + 'static>(
     }
 }
 
+fn mark_videos_as_watched<E: Env>(
+    library: &LibraryBucket,
+    meta_item: &MetaItem,
+    video_ids: Vec<&str>,
+    watched: bool,
+) -> Effects {
+    match library.items.get(&meta_item.id) {
+        Some(library_item) => {
+            let mut library_item = library_item.to_owned();
+            library_item.state.mark_videos_as_watched(
+                &meta_item.videos,
+                video_ids,
+                watched,
+                E::now(),
+            );
+            Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                .join(Effects::msg(Msg::Event(
+                    Event::LibraryItemMarkedAsWatched {
+                        id: meta_item.id.to_owned(),
+                        watched,
+                    },
+                )))
+                .unchanged()
+        }
+        _ => Effects::msg(Msg::Event(Event::Error {
+            error: CtxError::from(OtherError::LibraryItemNotFound),
+            source: Box::new(Event::LibraryItemMarkedAsWatched {
+                id: meta_item.id.to_owned(),
+                watched,
+            }),
+        }))
+        .unchanged(),
+    }
+}
+
 fn update_and_push_items_to_storage<E: Env + 'static>(
     library: &mut LibraryBucket,
     items: Vec<LibraryItem>,
@@ -234,18 +501,30 @@ fn update_and_push_items_to_storage<E: Env + 'static>(
         if are_items_in_recent {
             Either::Right(Either::Left(E::set_storage(
                 LIBRARY_RECENT_STORAGE_KEY,
-                Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
+                Some(&LibraryBucketRef::new(
+                    &library.uid,
+                    &recent_items,
+                    &library.last_synced,
+                )),
             )))
         } else {
             Either::Right(Either::Right(
                 future::try_join_all(vec![
                     E::set_storage(
                         LIBRARY_RECENT_STORAGE_KEY,
-                        Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
+                        Some(&LibraryBucketRef::new(
+                            &library.uid,
+                            &recent_items,
+                            &library.last_synced,
+                        )),
                     ),
                     E::set_storage(
                         LIBRARY_STORAGE_KEY,
-                        Some(&LibraryBucketRef::new(&library.uid, &other_items)),
+                        Some(&LibraryBucketRef::new(
+                            &library.uid,
+                            &other_items,
+                            &library.last_synced,
+                        )),
                     ),
                 ])
                 .map_ok(|_| ()),
@@ -270,11 +549,19 @@ fn push_library_to_storage<E: Env + 'static>(library: &LibraryBucket) -> Effect
     future::try_join_all(vec![
         E::set_storage(
             LIBRARY_RECENT_STORAGE_KEY,
-            Some(&LibraryBucketRef::new(&library.uid, &recent_items)),
+            Some(&LibraryBucketRef::new(
+                &library.uid,
+                &recent_items,
+                &library.last_synced,
+            )),
         ),
         E::set_storage(
             LIBRARY_STORAGE_KEY,
-            Some(&LibraryBucketRef::new(&library.uid, &other_items)),
+            Some(&LibraryBucketRef::new(
+                &library.uid,
+                &other_items,
+                &library.last_synced,
+            )),
         ),
     ])
     .map(move |result| match result {
@@ -288,30 +575,85 @@ fn push_library_to_storage<E: Env + 'static>(library: &LibraryBucket) -> Effect
     .into()
 }
 
-fn push_items_to_api<E: Env + 'static>(items: Vec<LibraryItem>, auth_key: &AuthKey) -> Effect {
-    let ids = items.iter().map(|item| &item.id).cloned().collect();
-    fetch_api::<E, _, SuccessResponse>(&DatastoreRequest {
+pub fn push_items_to_api<E: Env + 'static>(items: Vec<LibraryItem>, auth_key: &AuthKey) -> Effect {
+    let request = DatastoreRequest {
         auth_key: auth_key.to_owned(),
         collection: LIBRARY_COLLECTION_NAME.to_owned(),
         command: DatastoreCommand::Put { changes: items },
-    })
-    .map_err(CtxError::from)
-    .and_then(|result| match result {
-        APIResult::Ok { result } => future::ok(result),
-        APIResult::Err { error } => future::err(CtxError::from(error)),
-    })
-    .map(move |result| match result {
-        Ok(_) => Msg::Event(Event::LibraryItemsPushedToAPI { ids }),
-        Err(error) => Msg::Event(Event::Error {
-            error,
-            source: Box::new(Event::LibraryItemsPushedToAPI { ids }),
-        }),
-    })
-    .boxed_local()
-    .into()
+    };
+    fetch_api::<E, _, SuccessResponse>(&request)
+        .map_err(CtxError::from)
+        .and_then(|result| match result {
+            APIResult::Ok { .. } => future::ok(()),
+            APIResult::Err { error } => future::err(CtxError::from(error)),
+        })
+        .map(move |result| Msg::Internal(Internal::LibraryPushResult(request, result)))
+        .boxed_local()
+        .into()
+}
+
+fn sync_next_batch_with_api<E: Env + 'static>(
+    library: &mut LibraryBucket,
+    mut plan: LibrarySyncPlan,
+    auth_key: &AuthKey,
+) -> Effects {
+    let progress_effects = Effects::msg(Msg::Event(Event::LibrarySyncProgress {
+        pulled: plan.pulled,
+        pull_total: plan.pull_ids.len(),
+        pushed: plan.pushed,
+        push_total: plan.push_ids.len(),
+    }))
+    .unchanged();
+    if plan.pushed < plan.push_ids.len() {
+        let batch_end = cmp::min(plan.pushed + LIBRARY_SYNC_BATCH_SIZE, plan.push_ids.len());
+        let items = plan.push_ids[plan.pushed..batch_end]
+            .iter()
+            .filter_map(|id| library.items.get(id))
+            .cloned()
+            .collect();
+        plan.pushed = batch_end;
+        progress_effects
+            .join(Effects::one(push_sync_batch_to_api::<E>(items, plan, auth_key)).unchanged())
+    } else if plan.pulled < plan.pull_ids.len() {
+        let batch_end = cmp::min(plan.pulled + LIBRARY_SYNC_BATCH_SIZE, plan.pull_ids.len());
+        let ids = plan.pull_ids[plan.pulled..batch_end].to_vec();
+        plan.pulled = batch_end;
+        progress_effects
+            .join(Effects::one(pull_sync_batch_from_api::<E>(ids, plan, auth_key)).unchanged())
+    } else if library.last_synced != plan.synced_mtime {
+        library.last_synced = plan.synced_mtime;
+        progress_effects.join(Effects::msg(Msg::Internal(Internal::LibraryChanged(false))))
+    } else {
+        progress_effects
+    }
 }
 
-fn pull_items_from_api<E: Env + 'static>(ids: Vec<String>, auth_key: &AuthKey) -> Effect {
+fn push_sync_batch_to_api<E: Env + 'static>(
+    items: Vec<LibraryItem>,
+    plan: LibrarySyncPlan,
+    auth_key: &AuthKey,
+) -> Effect {
+    let request = DatastoreRequest {
+        auth_key: auth_key.to_owned(),
+        collection: LIBRARY_COLLECTION_NAME.to_owned(),
+        command: DatastoreCommand::Put { changes: items },
+    };
+    fetch_api::<E, _, SuccessResponse>(&request)
+        .map_err(CtxError::from)
+        .and_then(|result| match result {
+            APIResult::Ok { .. } => future::ok(()),
+            APIResult::Err { error } => future::err(CtxError::from(error)),
+        })
+        .map(move |result| Msg::Internal(Internal::LibrarySyncPushResult(request, plan, result)))
+        .boxed_local()
+        .into()
+}
+
+fn pull_sync_batch_from_api<E: Env + 'static>(
+    ids: Vec<String>,
+    plan: LibrarySyncPlan,
+    auth_key: &AuthKey,
+) -> Effect {
     let request = DatastoreRequest {
         auth_key: auth_key.to_owned(),
         collection: LIBRARY_COLLECTION_NAME.to_owned(),
@@ -323,12 +665,13 @@ fn pull_items_from_api<E: Env + 'static>(ids: Vec<String>, auth_key: &AuthKey) -
             APIResult::Ok { result } => future::ok(result),
             APIResult::Err { error } => future::err(CtxError::from(error)),
         })
-        .map(move |result| Msg::Internal(Internal::LibraryPullResult(request, result)))
+        .map(move |result| Msg::Internal(Internal::LibraryPullResult(request, plan, result)))
         .boxed_local()
         .into()
 }
 
-fn plan_sync_with_api<E: Env + 'static>(library: &LibraryBucket, auth_key: &AuthKey) -> Effect {
+pub fn plan_sync_with_api<E: Env + 'static>(library: &LibraryBucket, auth_key: &AuthKey) -> Effect {
+    let last_synced = library.last_synced;
     let local_mtimes = library
         .items
         .iter()
@@ -353,27 +696,34 @@ fn plan_sync_with_api<E: Env + 'static>(library: &LibraryBucket, auth_key: &Auth
                 .collect::<HashMap<_, _>>()
         })
         .map_ok(move |remote_mtimes| {
+            // mtimes are assigned by the clients and a device with a clock behind
+            // may modify items below the watermark, so every item is compared
             let pull_ids = remote_mtimes
                 .iter()
-                .filter(|(id, remote_mtime)| {
-                    local_mtimes
-                        .get(*id)
-                        .map_or(true, |local_mtime| local_mtime < remote_mtime)
+                .filter(|(id, remote_mtime)| match local_mtimes.get(*id) {
+                    Some(local_mtime) => local_mtime < remote_mtime,
+                    None => true,
                 })
                 .map(|(id, _)| id)
                 .cloned()
                 .collect();
             let push_ids = local_mtimes
                 .iter()
-                .filter(|(id, local_mtime)| {
-                    remote_mtimes
-                        .get(*id)
-                        .map_or(true, |remote_mtime| remote_mtime < local_mtime)
+                .filter(|(id, local_mtime)| match remote_mtimes.get(*id) {
+                    Some(remote_mtime) => remote_mtime < local_mtime,
+                    None => true,
                 })
                 .map(|(id, _)| id)
                 .cloned()
                 .collect();
-            (pull_ids, push_ids)
+            // the watermark only reports how far the library is synced
+            let synced_mtime = remote_mtimes.values().max().cloned().max(last_synced);
+            LibrarySyncPlan {
+                pull_ids,
+                push_ids,
+                synced_mtime,
+                ..Default::default()
+            }
         })
         .map(move |result| Msg::Internal(Internal::LibrarySyncPlanResult(request, result)))
         .boxed_local()
@@ -0,0 +1,223 @@
+use crate::constants::OUTBOX_STORAGE_KEY;
+use crate::models::ctx::{push_addons_to_api, push_items_to_api, CtxError, CtxStatus, OtherError};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::{Effect, Effects, Env, EnvError};
+use crate::types::api::{APIRequest, DatastoreCommand, DatastoreRequest};
+use crate::types::outbox::OutboxBucket;
+use crate::types::profile::{AuthKey, Profile};
+use futures::FutureExt;
+
+pub fn update_outbox<E: Env + 'static>(
+    outbox: &mut OutboxBucket,
+    profile: &Profile,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) => {
+            let next_outbox = OutboxBucket::default();
+            if *outbox != next_outbox {
+                *outbox = next_outbox;
+                Effects::msg(Msg::Internal(Internal::OutboxChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::FlushOutbox)) => match profile.auth_key() {
+            Some(auth_key) if outbox.uid == profile.uid() => flush_outbox::<E>(outbox, auth_key),
+            _ => Effects::msg(Msg::Event(Event::Error {
+                error: CtxError::from(OtherError::UserNotLoggedIn),
+                source: Box::new(Event::OutboxFlushed {
+                    ids: Default::default(),
+                    transport_urls: Default::default(),
+                }),
+            }))
+            .unchanged(),
+        },
+        Msg::Internal(Internal::OutboxChanged) => {
+            Effects::one(push_outbox_to_storage::<E>(outbox)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, ..)))
+                if loading_auth_request == auth_request =>
+            {
+                let uid = Some(auth.user.id.to_owned());
+                if outbox.uid != uid {
+                    *outbox = OutboxBucket::new(uid);
+                    Effects::msg(Msg::Internal(Internal::OutboxChanged))
+                } else {
+                    flush_outbox_if_due::<E>(outbox, profile)
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::LibraryPushResult(
+            DatastoreRequest {
+                auth_key,
+                command: DatastoreCommand::Put { changes },
+                ..
+            },
+            result,
+        ))
+        | Msg::Internal(Internal::LibrarySyncPushResult(
+            DatastoreRequest {
+                auth_key,
+                command: DatastoreCommand::Put { changes },
+                ..
+            },
+            _,
+            result,
+        )) if profile.auth_key() == Some(auth_key) => match result {
+            Ok(_) => {
+                let next_outbox = {
+                    let mut outbox = outbox.to_owned();
+                    outbox.remove_library_items(changes);
+                    outbox.reset_retry();
+                    outbox
+                };
+                let outbox_effects = if *outbox != next_outbox {
+                    *outbox = next_outbox;
+                    Effects::msg(Msg::Internal(Internal::OutboxChanged)).unchanged()
+                } else {
+                    Effects::none().unchanged()
+                };
+                outbox_effects.join(flush_outbox_if_due::<E>(outbox, profile))
+            }
+            Err(CtxError::Env(EnvError::Fetch(_))) => {
+                claim_outbox(outbox, profile);
+                outbox.merge_library_items(changes.to_owned());
+                schedule_outbox_retry::<E>(outbox);
+                Effects::msg(Msg::Internal(Internal::OutboxChanged)).unchanged()
+            }
+            Err(_) => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::AddonsPushResult(
+            APIRequest::AddonCollectionSet { auth_key, addons },
+            result,
+        )) if profile.auth_key() == Some(auth_key) => match result {
+            Ok(_) => {
+                let next_outbox = {
+                    let mut outbox = outbox.to_owned();
+                    outbox.addons = None;
+                    outbox.reset_retry();
+                    outbox
+                };
+                let outbox_effects = if *outbox != next_outbox {
+                    *outbox = next_outbox;
+                    Effects::msg(Msg::Internal(Internal::OutboxChanged)).unchanged()
+                } else {
+                    Effects::none().unchanged()
+                };
+                outbox_effects.join(flush_outbox_if_due::<E>(outbox, profile))
+            }
+            Err(CtxError::Env(EnvError::Fetch(_))) => {
+                claim_outbox(outbox, profile);
+                outbox.addons = Some(addons.to_owned());
+                schedule_outbox_retry::<E>(outbox);
+                Effects::msg(Msg::Internal(Internal::OutboxChanged)).unchanged()
+            }
+            Err(_) => Effects::none().unchanged(),
+        },
+        Msg::Internal(Internal::LibraryPullResult(DatastoreRequest { auth_key, .. }, _, Ok(_)))
+        | Msg::Internal(Internal::LibrarySyncPlanResult(
+            DatastoreRequest { auth_key, .. },
+            Ok(_),
+        ))
+        | Msg::Internal(Internal::AddonsAPIResult(
+            APIRequest::AddonCollectionGet { auth_key, .. },
+            Ok(_),
+        ))
+        | Msg::Internal(Internal::UserAPIResult(APIRequest::GetUser { auth_key }, Ok(_)))
+            if profile.auth_key() == Some(auth_key) =>
+        {
+            flush_outbox_if_due::<E>(outbox, profile)
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+// Failed pushes belong to the current user, stale items of another user are dropped.
+fn claim_outbox(outbox: &mut OutboxBucket, profile: &Profile) {
+    let uid = profile.uid();
+    if outbox.uid != uid {
+        *outbox = OutboxBucket::new(uid);
+    };
+}
+
+fn schedule_outbox_retry<E: Env>(outbox: &mut OutboxBucket) {
+    let now = E::now();
+    if outbox
+        .retry_after
+        .iter()
+        .all(|retry_after| *retry_after <= now)
+    {
+        outbox.schedule_retry(now);
+    };
+}
+
+fn flush_outbox_if_due<E: Env + 'static>(outbox: &mut OutboxBucket, profile: &Profile) -> Effects {
+    match profile.auth_key() {
+        Some(auth_key) if outbox.uid == profile.uid() && outbox.is_due(E::now()) => {
+            flush_outbox::<E>(outbox, auth_key)
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn flush_outbox<E: Env + 'static>(outbox: &mut OutboxBucket, auth_key: &AuthKey) -> Effects {
+    if outbox.is_empty() {
+        return Effects::msg(Msg::Event(Event::OutboxFlushed {
+            ids: vec![],
+            transport_urls: vec![],
+        }))
+        .unchanged();
+    };
+    let ids = outbox.library_items.keys().cloned().collect();
+    let transport_urls = outbox
+        .addons
+        .iter()
+        .flatten()
+        .map(|addon| &addon.transport_url)
+        .cloned()
+        .collect();
+    let push_items_to_api_effects = if outbox.library_items.is_empty() {
+        Effects::none().unchanged()
+    } else {
+        Effects::one(push_items_to_api::<E>(
+            outbox.library_items.values().cloned().collect(),
+            auth_key,
+        ))
+        .unchanged()
+    };
+    let push_addons_to_api_effects = match &outbox.addons {
+        Some(addons) => {
+            Effects::one(push_addons_to_api::<E>(addons.to_owned(), auth_key)).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    };
+    outbox.schedule_retry(E::now());
+    Effects::msg(Msg::Event(Event::OutboxFlushed {
+        ids,
+        transport_urls,
+    }))
+    .unchanged()
+    .join(push_items_to_api_effects)
+    .join(push_addons_to_api_effects)
+    .join(Effects::msg(Msg::Internal(Internal::OutboxChanged)).unchanged())
+}
+
+fn push_outbox_to_storage<E: Env + 'static>(outbox: &OutboxBucket) -> Effect {
+    E::set_storage(OUTBOX_STORAGE_KEY, Some(outbox))
+        .map({
+            let uid = outbox.uid.to_owned();
+            move |result| match result {
+                Ok(_) => Msg::Event(Event::OutboxPushedToStorage { uid }),
+                Err(error) => Msg::Event(Event::Error {
+                    error: CtxError::from(error),
+                    source: Box::new(Event::OutboxPushedToStorage { uid }),
+                }),
+            }
+        })
+        .boxed_local()
+        .into()
+}
@@ -1,10 +1,14 @@
+use crate::addon_transport::clear_addon_resource_cache;
 use crate::constants::{OFFICIAL_ADDONS, PROFILE_STORAGE_KEY};
 use crate::models::ctx::{CtxError, CtxStatus, OtherError};
 use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
 use crate::runtime::{Effect, Effects, Env};
 use crate::types::addon::Descriptor;
-use crate::types::api::{fetch_api, APIRequest, APIResult, CollectionResponse, SuccessResponse};
-use crate::types::profile::{AuthKey, Profile, Settings};
+use crate::types::api::{
+    fetch_api, APIRequest, APIResult, CollectionResponse, SettingsResponse, SuccessResponse,
+};
+use crate::types::profile::{Auth, AuthKey, Profile, Settings, User};
+use chrono::{DateTime, Utc};
 use enclose::enclose;
 use futures::{future, FutureExt, TryFutureExt};
 
@@ -23,14 +27,24 @@ pub fn update_profile<E: Env + 'static>(
                 Effects::none().unchanged()
             }
         }
-        Msg::Action(Action::Ctx(ActionCtx::PushUserToAPI)) => {
-            // TODO implement
-            Effects::msg(Msg::Event(Event::UserPushedToAPI { uid: profile.uid() })).unchanged()
-        }
-        Msg::Action(Action::Ctx(ActionCtx::PullUserFromAPI)) => {
-            // TODO implement
-            Effects::msg(Msg::Event(Event::UserPulledFromAPI { uid: profile.uid() })).unchanged()
-        }
+        Msg::Action(Action::Ctx(ActionCtx::PushUserToAPI)) => match &profile.auth {
+            Some(Auth { key, user }) => {
+                Effects::one(push_user_to_api::<E>(user.to_owned(), key)).unchanged()
+            }
+            _ => Effects::msg(Msg::Event(Event::Error {
+                error: CtxError::from(OtherError::UserNotLoggedIn),
+                source: Box::new(Event::UserPushedToAPI { uid: profile.uid() }),
+            }))
+            .unchanged(),
+        },
+        Msg::Action(Action::Ctx(ActionCtx::PullUserFromAPI)) => match profile.auth_key() {
+            Some(auth_key) => Effects::one(pull_user_from_api::<E>(auth_key)).unchanged(),
+            _ => Effects::msg(Msg::Event(Event::Error {
+                error: CtxError::from(OtherError::UserNotLoggedIn),
+                source: Box::new(Event::UserPulledFromAPI { uid: profile.uid() }),
+            }))
+            .unchanged(),
+        },
         Msg::Action(Action::Ctx(ActionCtx::PushAddonsToAPI)) => match profile.auth_key() {
             Some(auth_key) => {
                 Effects::one(push_addons_to_api::<E>(profile.addons.to_owned(), auth_key))
@@ -182,10 +196,22 @@ pub fn update_profile<E: Env + 'static>(
         }
         Msg::Action(Action::Ctx(ActionCtx::UpdateSettings(settings))) => {
             if profile.settings != *settings {
+                let last_modified = E::now();
                 profile.settings = settings.to_owned();
+                profile.settings_last_modified = Some(last_modified);
+                let push_to_api_effects = match profile.auth_key() {
+                    Some(auth_key) => Effects::one(push_settings_to_api::<E>(
+                        settings.to_owned(),
+                        last_modified,
+                        auth_key,
+                    ))
+                    .unchanged(),
+                    _ => Effects::none().unchanged(),
+                };
                 Effects::msg(Msg::Event(Event::SettingsUpdated {
                     settings: settings.to_owned(),
                 }))
+                .join(push_to_api_effects)
                 .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
             } else {
                 Effects::msg(Msg::Event(Event::SettingsUpdated {
@@ -195,26 +221,108 @@ pub fn update_profile<E: Env + 'static>(
             }
         }
         Msg::Internal(Internal::ProfileChanged) => {
+            clear_addon_resource_cache();
             Effects::one(push_profile_to_storage::<E>(profile)).unchanged()
         }
         Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
-            (CtxStatus::Loading(loading_auth_request), Ok((auth, addons, _)))
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, addons, _, remote_settings)))
                 if loading_auth_request == auth_request =>
             {
+                let (settings_effects, settings, settings_last_modified) = match remote_settings {
+                    Some(SettingsResponse {
+                        settings,
+                        last_modified,
+                    }) if profile
+                        .settings_last_modified
+                        .iter()
+                        .all(|local_last_modified| local_last_modified <= last_modified) =>
+                    {
+                        (
+                            Effects::msg(Msg::Event(Event::SettingsPulledFromAPI {
+                                settings: settings.to_owned(),
+                            }))
+                            .unchanged(),
+                            settings.to_owned(),
+                            Some(last_modified.to_owned()),
+                        )
+                    }
+                    _ => match profile.settings_last_modified {
+                        Some(last_modified) => (
+                            Effects::one(push_settings_to_api::<E>(
+                                profile.settings.to_owned(),
+                                last_modified,
+                                &auth.key,
+                            ))
+                            .unchanged(),
+                            profile.settings.to_owned(),
+                            Some(last_modified),
+                        ),
+                        _ => (
+                            Effects::none().unchanged(),
+                            profile.settings.to_owned(),
+                            None,
+                        ),
+                    },
+                };
                 let next_proifle = Profile {
                     auth: Some(auth.to_owned()),
                     addons: addons.to_owned(),
-                    settings: Settings::default(),
+                    settings,
+                    settings_last_modified,
                 };
                 if *profile != next_proifle {
                     *profile = next_proifle;
-                    Effects::msg(Msg::Internal(Internal::ProfileChanged))
+                    settings_effects.join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
                 } else {
-                    Effects::none().unchanged()
+                    settings_effects
                 }
             }
             _ => Effects::none().unchanged(),
         },
+        Msg::Internal(Internal::UserAPIResult(APIRequest::GetUser { auth_key }, result))
+            if profile.auth_key() == Some(auth_key) =>
+        {
+            match result {
+                Ok(user) => {
+                    let uid = profile.uid();
+                    match &mut profile.auth {
+                        Some(auth)
+                            if auth.user != *user
+                                && auth.user.last_modified <= user.last_modified =>
+                        {
+                            auth.user = user.to_owned();
+                            Effects::msg(Msg::Event(Event::UserPulledFromAPI { uid }))
+                                .join(Effects::msg(Msg::Internal(Internal::ProfileChanged)))
+                        }
+                        _ => Effects::msg(Msg::Event(Event::UserPulledFromAPI { uid })).unchanged(),
+                    }
+                }
+                Err(error) => Effects::msg(Msg::Event(Event::Error {
+                    error: error.to_owned(),
+                    source: Box::new(Event::UserPulledFromAPI { uid: profile.uid() }),
+                }))
+                .unchanged(),
+            }
+        }
+        Msg::Internal(Internal::AddonsPushResult(
+            APIRequest::AddonCollectionSet { addons, .. },
+            result,
+        )) => {
+            let transport_urls = addons
+                .iter()
+                .map(|addon| &addon.transport_url)
+                .cloned()
+                .collect();
+            match result {
+                Ok(_) => Effects::msg(Msg::Event(Event::AddonsPushedToAPI { transport_urls }))
+                    .unchanged(),
+                Err(error) => Effects::msg(Msg::Event(Event::Error {
+                    error: error.to_owned(),
+                    source: Box::new(Event::AddonsPushedToAPI { transport_urls }),
+                }))
+                .unchanged(),
+            }
+        }
         Msg::Internal(Internal::AddonsAPIResult(
             APIRequest::AddonCollectionGet { auth_key, .. },
             result,
@@ -246,15 +354,53 @@ pub fn update_profile<E: Env + 'static>(
     }
 }
 
-fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &AuthKey) -> Effect {
-    let transport_urls = addons
-        .iter()
-        .map(|addon| &addon.transport_url)
-        .cloned()
-        .collect();
-    let request = APIRequest::AddonCollectionSet {
+fn push_user_to_api<E: Env + 'static>(user: User, auth_key: &AuthKey) -> Effect {
+    let uid = Some(user.id.to_owned());
+    let request = APIRequest::SaveUser {
         auth_key: auth_key.to_owned(),
-        addons,
+        user,
+    };
+    fetch_api::<E, _, SuccessResponse>(&request)
+        .map_err(CtxError::from)
+        .and_then(|result| match result {
+            APIResult::Ok { result } => future::ok(result),
+            APIResult::Err { error } => future::err(CtxError::from(error)),
+        })
+        .map(move |result| match result {
+            Ok(_) => Msg::Event(Event::UserPushedToAPI { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error,
+                source: Box::new(Event::UserPushedToAPI { uid }),
+            }),
+        })
+        .boxed_local()
+        .into()
+}
+
+fn pull_user_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
+    let request = APIRequest::GetUser {
+        auth_key: auth_key.to_owned(),
+    };
+    fetch_api::<E, _, _>(&request)
+        .map_err(CtxError::from)
+        .and_then(|result| match result {
+            APIResult::Ok { result } => future::ok(result),
+            APIResult::Err { error } => future::err(CtxError::from(error)),
+        })
+        .map(move |result| Msg::Internal(Internal::UserAPIResult(request, result)))
+        .boxed_local()
+        .into()
+}
+
+fn push_settings_to_api<E: Env + 'static>(
+    settings: Settings,
+    last_modified: DateTime<Utc>,
+    auth_key: &AuthKey,
+) -> Effect {
+    let request = APIRequest::SettingsSet {
+        auth_key: auth_key.to_owned(),
+        settings: settings.to_owned(),
+        last_modified,
     };
     fetch_api::<E, _, SuccessResponse>(&request)
         .map_err(CtxError::from)
@@ -263,16 +409,32 @@ fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &Auth
             APIResult::Err { error } => future::err(CtxError::from(error)),
         })
         .map(move |result| match result {
-            Ok(_) => Msg::Event(Event::AddonsPushedToAPI { transport_urls }),
+            Ok(_) => Msg::Event(Event::SettingsPushedToAPI { settings }),
             Err(error) => Msg::Event(Event::Error {
                 error,
-                source: Box::new(Event::AddonsPushedToAPI { transport_urls }),
+                source: Box::new(Event::SettingsPushedToAPI { settings }),
             }),
         })
         .boxed_local()
         .into()
 }
 
+pub fn push_addons_to_api<E: Env + 'static>(addons: Vec<Descriptor>, auth_key: &AuthKey) -> Effect {
+    let request = APIRequest::AddonCollectionSet {
+        auth_key: auth_key.to_owned(),
+        addons,
+    };
+    fetch_api::<E, _, SuccessResponse>(&request)
+        .map_err(CtxError::from)
+        .and_then(|result| match result {
+            APIResult::Ok { .. } => future::ok(()),
+            APIResult::Err { error } => future::err(CtxError::from(error)),
+        })
+        .map(move |result| Msg::Internal(Internal::AddonsPushResult(request, result)))
+        .boxed_local()
+        .into()
+}
+
 fn pull_addons_from_api<E: Env + 'static>(auth_key: &AuthKey) -> Effect {
     let request = APIRequest::AddonCollectionGet {
         auth_key: auth_key.to_owned(),
@@ -0,0 +1,80 @@
+use crate::constants::SEARCH_HISTORY_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::{Effect, Effects, Env};
+use crate::types::search_history::SearchHistoryBucket;
+use futures::FutureExt;
+
+pub fn update_search_history<E: Env + 'static>(
+    search_history: &mut SearchHistoryBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) => {
+            let next_search_history = SearchHistoryBucket::default();
+            if *search_history != next_search_history {
+                *search_history = next_search_history;
+                Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Action(Action::Ctx(ActionCtx::ClearSearchHistory)) => {
+            let next_search_history = SearchHistoryBucket::new(search_history.uid.to_owned());
+            let search_history_effects = if *search_history != next_search_history {
+                *search_history = next_search_history;
+                Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            };
+            search_history_effects.join(Effects::msg(Msg::Event(Event::SearchHistoryCleared {
+                uid: search_history.uid.to_owned(),
+            })))
+        }
+        Msg::Internal(Internal::UpdateSearchHistory(query)) => {
+            let mut next_search_history = search_history.to_owned();
+            next_search_history.record(query, E::now());
+            if *search_history != next_search_history {
+                *search_history = next_search_history;
+                Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::SearchHistoryChanged) => {
+            Effects::one(push_search_history_to_storage::<E>(search_history)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, ..)))
+                if loading_auth_request == auth_request =>
+            {
+                let next_search_history = SearchHistoryBucket::new(Some(auth.user.id.to_owned()));
+                if search_history.uid != next_search_history.uid {
+                    *search_history = next_search_history;
+                    Effects::msg(Msg::Internal(Internal::SearchHistoryChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_search_history_to_storage<E: Env + 'static>(
+    search_history: &SearchHistoryBucket,
+) -> Effect {
+    let uid = search_history.uid.to_owned();
+    E::set_storage(SEARCH_HISTORY_STORAGE_KEY, Some(search_history))
+        .map(move |result| match result {
+            Ok(_) => Msg::Event(Event::SearchHistoryPushedToStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::SearchHistoryPushedToStorage { uid }),
+            }),
+        })
+        .boxed_local()
+        .into()
+}
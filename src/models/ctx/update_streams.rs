@@ -0,0 +1,62 @@
+use crate::constants::STREAMS_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::{Effect, Effects, Env};
+use crate::types::streams::StreamsBucket;
+use futures::FutureExt;
+
+pub fn update_streams<E: Env + 'static>(
+    streams: &mut StreamsBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) => {
+            let next_streams = StreamsBucket::default();
+            if *streams != next_streams {
+                *streams = next_streams;
+                Effects::msg(Msg::Internal(Internal::StreamsChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::UpdateStreamsItem(streams_item)) => {
+            let mut streams_item = streams_item.to_owned();
+            streams_item.mtime = E::now();
+            streams.insert(streams_item);
+            Effects::msg(Msg::Internal(Internal::StreamsChanged))
+        }
+        Msg::Internal(Internal::StreamsChanged) => {
+            Effects::one(push_streams_to_storage::<E>(streams)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, ..)))
+                if loading_auth_request == auth_request =>
+            {
+                let next_streams = StreamsBucket::new(Some(auth.user.id.to_owned()));
+                if streams.uid != next_streams.uid {
+                    *streams = next_streams;
+                    Effects::msg(Msg::Internal(Internal::StreamsChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_streams_to_storage<E: Env + 'static>(streams: &StreamsBucket) -> Effect {
+    let uid = streams.uid.to_owned();
+    E::set_storage(STREAMS_STORAGE_KEY, Some(streams))
+        .map(move |result| match result {
+            Ok(_) => Msg::Event(Event::StreamsPushedToStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::StreamsPushedToStorage { uid }),
+            }),
+        })
+        .boxed_local()
+        .into()
+}
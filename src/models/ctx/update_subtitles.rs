@@ -0,0 +1,62 @@
+use crate::constants::SUBTITLES_STORAGE_KEY;
+use crate::models::ctx::{CtxError, CtxStatus};
+use crate::runtime::msg::{Action, ActionCtx, Event, Internal, Msg};
+use crate::runtime::{Effect, Effects, Env};
+use crate::types::subtitles::SubtitlesBucket;
+use futures::FutureExt;
+
+pub fn update_subtitles<E: Env + 'static>(
+    subtitles: &mut SubtitlesBucket,
+    status: &CtxStatus,
+    msg: &Msg,
+) -> Effects {
+    match msg {
+        Msg::Action(Action::Ctx(ActionCtx::Logout)) => {
+            let next_subtitles = SubtitlesBucket::default();
+            if *subtitles != next_subtitles {
+                *subtitles = next_subtitles;
+                Effects::msg(Msg::Internal(Internal::SubtitlesChanged))
+            } else {
+                Effects::none().unchanged()
+            }
+        }
+        Msg::Internal(Internal::UpdateSubtitlesItem(subtitles_item)) => {
+            let mut subtitles_item = subtitles_item.to_owned();
+            subtitles_item.mtime = E::now();
+            subtitles.insert(subtitles_item);
+            Effects::msg(Msg::Internal(Internal::SubtitlesChanged))
+        }
+        Msg::Internal(Internal::SubtitlesChanged) => {
+            Effects::one(push_subtitles_to_storage::<E>(subtitles)).unchanged()
+        }
+        Msg::Internal(Internal::CtxAuthResult(auth_request, result)) => match (status, result) {
+            (CtxStatus::Loading(loading_auth_request), Ok((auth, ..)))
+                if loading_auth_request == auth_request =>
+            {
+                let next_subtitles = SubtitlesBucket::new(Some(auth.user.id.to_owned()));
+                if subtitles.uid != next_subtitles.uid {
+                    *subtitles = next_subtitles;
+                    Effects::msg(Msg::Internal(Internal::SubtitlesChanged))
+                } else {
+                    Effects::none().unchanged()
+                }
+            }
+            _ => Effects::none().unchanged(),
+        },
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn push_subtitles_to_storage<E: Env + 'static>(subtitles: &SubtitlesBucket) -> Effect {
+    let uid = subtitles.uid.to_owned();
+    E::set_storage(SUBTITLES_STORAGE_KEY, Some(subtitles))
+        .map(move |result| match result {
+            Ok(_) => Msg::Event(Event::SubtitlesPushedToStorage { uid }),
+            Err(error) => Msg::Event(Event::Error {
+                error: CtxError::from(error),
+                source: Box::new(Event::SubtitlesPushedToStorage { uid }),
+            }),
+        })
+        .boxed_local()
+        .into()
+}
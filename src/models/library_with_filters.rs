@@ -1,7 +1,7 @@
 use crate::constants::{CATALOG_PAGE_SIZE, TYPE_PRIORITIES};
 use crate::models::common::{compare_with_priorities, eq_update};
 use crate::models::ctx::Ctx;
-use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::msg::{Action, ActionLibraryWithFilters, ActionLoad, Internal, Msg};
 use crate::runtime::{Effects, Env, UpdateWithCtx};
 use crate::types::library::{LibraryBucket, LibraryItem};
 use boolinator::Boolinator;
@@ -9,11 +9,14 @@ use derivative::Derivative;
 use derive_more::Deref;
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::iter;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
 
 pub trait LibraryFilter {
     fn predicate(library_item: &LibraryItem) -> bool;
@@ -35,6 +38,14 @@ impl LibraryFilter for NotRemovedFilter {
     }
 }
 
+pub enum RemovedFilter {}
+
+impl LibraryFilter for RemovedFilter {
+    fn predicate(library_item: &LibraryItem) -> bool {
+        library_item.removed && !library_item.temp
+    }
+}
+
 #[derive(Derivative, Clone, PartialEq, EnumIter, Serialize, Deserialize)]
 #[derivative(Default)]
 #[serde(rename_all = "lowercase")]
@@ -43,6 +54,25 @@ pub enum Sort {
     LastWatched,
     Name,
     TimesWatched,
+    DateAdded,
+    Watched,
+    Duration,
+}
+
+impl Sort {
+    pub fn default_order(&self) -> SortOrder {
+        match self {
+            Sort::Name => SortOrder::Ascending,
+            _ => SortOrder::Descending,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, EnumIter, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Ascending,
+    Descending,
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -51,9 +81,21 @@ pub struct LibraryRequest {
     #[serde(default)]
     pub sort: Sort,
     #[serde(default)]
+    pub order: Option<SortOrder>,
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
     pub page: LibraryRequestPage,
 }
 
+impl LibraryRequest {
+    pub fn order(&self) -> SortOrder {
+        self.order
+            .to_owned()
+            .unwrap_or_else(|| self.sort.default_order())
+    }
+}
+
 #[derive(Clone, Deref, PartialEq, Serialize, Deserialize)]
 pub struct LibraryRequestPage(pub NonZeroUsize);
 
@@ -78,6 +120,7 @@ pub struct SelectableType {
 #[derive(PartialEq, Serialize)]
 pub struct SelectableSort {
     pub sort: Sort,
+    pub order: SortOrder,
     pub selected: bool,
     pub request: LibraryRequest,
 }
@@ -144,6 +187,22 @@ impl<E: Env + 'static, F: LibraryFilter> UpdateWithCtx<E> for LibraryWithFilters
                     .join(selectable_effects)
                     .join(catalog_effects)
             }
+            Msg::Action(Action::LibraryWithFilters(
+                ActionLibraryWithFilters::ToggleNotifications(id),
+            )) => match self
+                .catalog
+                .iter()
+                .find(|library_item| library_item.id == *id)
+            {
+                Some(library_item) => {
+                    Effects::msg(Msg::Internal(Internal::SetLibraryItemNotifications {
+                        id: id.to_owned(),
+                        no_notif: !library_item.state.no_notif,
+                    }))
+                    .unchanged()
+                }
+                _ => Effects::none().unchanged(),
+            },
             Msg::Internal(Internal::LibraryChanged(_)) => {
                 let selectable_effects =
                     selectable_update::<F>(&mut self.selectable, &self.selected, &ctx.library);
@@ -179,6 +238,12 @@ fn selectable_update<F: LibraryFilter>(
                     .as_ref()
                     .map(|selected| selected.request.sort.to_owned())
                     .unwrap_or_default(),
+                order: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.order.to_owned()),
+                search: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.search.to_owned()),
                 page: LibraryRequestPage::default(),
             },
             selected: selected
@@ -195,6 +260,12 @@ fn selectable_update<F: LibraryFilter>(
                 .as_ref()
                 .map(|selected| selected.request.sort.to_owned())
                 .unwrap_or_default(),
+            order: selected
+                .as_ref()
+                .and_then(|selected| selected.request.order.to_owned()),
+            search: selected
+                .as_ref()
+                .and_then(|selected| selected.request.search.to_owned()),
             page: LibraryRequestPage::default(),
         },
         selected: selected
@@ -205,18 +276,24 @@ fn selectable_update<F: LibraryFilter>(
     .chain(selectable_types.into_iter())
     .collect::<Vec<_>>();
     let selectable_sorts = Sort::iter()
-        .map(|sort| SelectableSort {
+        .cartesian_product(SortOrder::iter())
+        .map(|(sort, order)| SelectableSort {
             sort: sort.to_owned(),
+            order: order.to_owned(),
             request: LibraryRequest {
                 r#type: selected
                     .as_ref()
                     .and_then(|selected| selected.request.r#type.to_owned()),
                 sort: sort.to_owned(),
+                order: Some(order.to_owned()),
+                search: selected
+                    .as_ref()
+                    .and_then(|selected| selected.request.search.to_owned()),
                 page: LibraryRequestPage::default(),
             },
             selected: selected
                 .as_ref()
-                .map(|selected| selected.request.sort == sort)
+                .map(|selected| selected.request.sort == sort && selected.request.order() == order)
                 .unwrap_or_default(),
         })
         .collect();
@@ -232,14 +309,7 @@ fn selectable_update<F: LibraryFilter>(
                         ..selected.request.to_owned()
                     },
                 });
-            let next_page = library
-                .items
-                .values()
-                .filter(|library_item| F::predicate(library_item))
-                .filter(|library_item| match &selected.request.r#type {
-                    Some(r#type) => library_item.r#type == *r#type,
-                    None => true,
-                })
+            let next_page = library_items::<F>(library, &selected.request)
                 .nth(selected.request.page.get() * CATALOG_PAGE_SIZE)
                 .map(|_| SelectablePage {
                     request: LibraryRequest {
@@ -268,18 +338,23 @@ fn catalog_update<F: LibraryFilter>(
     library: &LibraryBucket,
 ) -> Effects {
     let next_catalog = match selected {
-        Some(selected) => library
-            .items
-            .values()
-            .filter(|library_item| F::predicate(library_item))
-            .filter(|library_item| match &selected.request.r#type {
-                Some(r#type) => library_item.r#type == *r#type,
-                None => true,
-            })
-            .sorted_by(|a, b| match &selected.request.sort {
-                Sort::LastWatched => b.state.last_watched.cmp(&a.state.last_watched),
-                Sort::TimesWatched => b.state.times_watched.cmp(&a.state.times_watched),
-                Sort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        Some(selected) => library_items::<F>(library, &selected.request)
+            .sorted_by(|a, b| {
+                let ordering = match &selected.request.sort {
+                    Sort::LastWatched => a.state.last_watched.cmp(&b.state.last_watched),
+                    Sort::TimesWatched => a.state.times_watched.cmp(&b.state.times_watched),
+                    Sort::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                    Sort::DateAdded => a.ctime.cmp(&b.ctime),
+                    Sort::Watched => a
+                        .progress()
+                        .partial_cmp(&b.progress())
+                        .unwrap_or(Ordering::Equal),
+                    Sort::Duration => a.state.duration.cmp(&b.state.duration),
+                };
+                match selected.request.order() {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
             })
             .skip((selected.request.page.get() - 1) * CATALOG_PAGE_SIZE)
             .take(CATALOG_PAGE_SIZE)
@@ -289,3 +364,30 @@ fn catalog_update<F: LibraryFilter>(
     };
     eq_update(catalog, next_catalog)
 }
+
+fn library_items<'a, F: LibraryFilter>(
+    library: &'a LibraryBucket,
+    request: &'a LibraryRequest,
+) -> impl Iterator<Item = &'a LibraryItem> {
+    let search = request.search.as_deref().map(normalize_search);
+    library
+        .items
+        .values()
+        .filter(|library_item| F::predicate(library_item))
+        .filter(move |library_item| match &request.r#type {
+            Some(r#type) => library_item.r#type == *r#type,
+            None => true,
+        })
+        .filter(move |library_item| match &search {
+            Some(search) => normalize_search(&library_item.name).contains(search.as_str()),
+            None => true,
+        })
+}
+
+fn normalize_search(value: &str) -> String {
+    value
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
@@ -4,10 +4,14 @@ use crate::models::common::{
     ResourcesAction,
 };
 use crate::models::ctx::Ctx;
-use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::msg::{Action, ActionLoad, ActionMetaDetails, Internal, Msg};
 use crate::runtime::{Effects, Env, UpdateWithCtx};
 use crate::types::addon::{AggrRequest, ResourcePath};
-use crate::types::resource::{MetaItem, Stream};
+use crate::types::library::{LibraryBucket, LibraryItem};
+use crate::types::resource::{MetaItem, Stream, Video};
+use crate::types::streams::StreamsBucket;
+use crate::types::watched_bitfield::WatchedBitField;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -17,12 +21,40 @@ pub struct Selected {
     pub stream_path: Option<ResourcePath>,
 }
 
+#[derive(Clone, Copy, PartialEq, Serialize)]
+pub enum VideoStatus {
+    Watched,
+    Unwatched,
+    Upcoming,
+    InProgress,
+}
+
+#[derive(PartialEq, Serialize)]
+pub struct SelectableVideo {
+    pub video: Video,
+    pub status: VideoStatus,
+}
+
+#[derive(PartialEq, Serialize)]
+pub struct SelectableSeason {
+    pub season: u32,
+    pub selected: bool,
+    pub videos: Vec<SelectableVideo>,
+}
+
+#[derive(Default, PartialEq, Serialize)]
+pub struct SeasonsSelectable {
+    pub seasons: Vec<SelectableSeason>,
+}
+
 #[derive(Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MetaDetails {
     pub selected: Option<Selected>,
     pub meta_items: Vec<ResourceLoadable<MetaItem>>,
     pub streams: Vec<ResourceLoadable<Vec<Stream>>>,
+    pub suggested_stream: Option<Stream>,
+    pub seasons_selectable: SeasonsSelectable,
 }
 
 impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
@@ -55,17 +87,46 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     }
                     None => eq_update(&mut self.streams, vec![]),
                 };
+                let suggested_stream_effects = suggested_stream_update(
+                    &mut self.suggested_stream,
+                    &self.selected,
+                    &self.streams,
+                    &ctx.streams,
+                );
+                let seasons_selectable_effects = seasons_selectable_update::<E>(
+                    &mut self.seasons_selectable,
+                    None,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.library,
+                );
                 selected_effects
                     .join(meta_items_effects)
                     .join(streams_effects)
+                    .join(suggested_stream_effects)
+                    .join(seasons_selectable_effects)
             }
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
                 let meta_items_effects = eq_update(&mut self.meta_items, vec![]);
                 let streams_effects = eq_update(&mut self.streams, vec![]);
+                let suggested_stream_effects = eq_update(&mut self.suggested_stream, None);
+                let seasons_selectable_effects =
+                    eq_update(&mut self.seasons_selectable, SeasonsSelectable::default());
                 selected_effects
                     .join(meta_items_effects)
                     .join(streams_effects)
+                    .join(suggested_stream_effects)
+                    .join(seasons_selectable_effects)
+            }
+            Msg::Action(Action::MetaDetails(ActionMetaDetails::SelectSeason(season))) => {
+                seasons_selectable_update::<E>(
+                    &mut self.seasons_selectable,
+                    Some(*season),
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.library,
+                )
             }
             Msg::Internal(Internal::ResourceRequestResult(request, result))
                 if request.path.resource == META_RESOURCE_NAME =>
@@ -93,20 +154,59 @@ impl<E: Env + 'static> UpdateWithCtx<E> for MetaDetails {
                     }
                     _ => Effects::none().unchanged(),
                 };
-                meta_items_effects.join(streams_effects)
+                let suggested_stream_effects = suggested_stream_update(
+                    &mut self.suggested_stream,
+                    &self.selected,
+                    &self.streams,
+                    &ctx.streams,
+                );
+                let seasons_selectable_effects = seasons_selectable_update::<E>(
+                    &mut self.seasons_selectable,
+                    None,
+                    &self.selected,
+                    &self.meta_items,
+                    &ctx.library,
+                );
+                let last_vid_released_effects =
+                    last_vid_released_update::<E>(&self.selected, &self.meta_items, &ctx.library);
+                meta_items_effects
+                    .join(streams_effects)
+                    .join(suggested_stream_effects)
+                    .join(seasons_selectable_effects)
+                    .join(last_vid_released_effects)
             }
             Msg::Internal(Internal::ResourceRequestResult(request, result))
                 if request.path.resource == STREAM_RESOURCE_NAME =>
             {
-                resources_update_with_vector_content::<E, _>(
+                let streams_effects = resources_update_with_vector_content::<E, _>(
                     &mut self.streams,
                     ResourcesAction::ResourceRequestResult {
                         request,
                         result,
                         limit: &None,
                     },
-                )
+                );
+                let suggested_stream_effects = suggested_stream_update(
+                    &mut self.suggested_stream,
+                    &self.selected,
+                    &self.streams,
+                    &ctx.streams,
+                );
+                streams_effects.join(suggested_stream_effects)
             }
+            Msg::Internal(Internal::LibraryChanged(_)) => seasons_selectable_update::<E>(
+                &mut self.seasons_selectable,
+                None,
+                &self.selected,
+                &self.meta_items,
+                &ctx.library,
+            ),
+            Msg::Internal(Internal::StreamsChanged) => suggested_stream_update(
+                &mut self.suggested_stream,
+                &self.selected,
+                &self.streams,
+                &ctx.streams,
+            ),
             _ => Effects::none().unchanged(),
         }
     }
@@ -137,3 +237,198 @@ fn streams_from_meta_items(
             content: Loadable::Ready(streams.to_owned()),
         })
 }
+
+fn suggested_stream_update(
+    suggested_stream: &mut Option<Stream>,
+    selected: &Option<Selected>,
+    streams: &[ResourceLoadable<Vec<Stream>>],
+    streams_bucket: &StreamsBucket,
+) -> Effects {
+    let next_suggested_stream = match selected {
+        Some(Selected {
+            meta_path,
+            stream_path: Some(stream_path),
+        }) => match streams_bucket.get(&meta_path.id, &stream_path.id) {
+            Some(streams_item) => Some(streams_item.stream.to_owned()),
+            None => streams_bucket
+                .last_for_meta(&meta_path.id)
+                .and_then(|streams_item| {
+                    streams_item
+                        .stream
+                        .behavior_hints
+                        .binge_group
+                        .as_ref()
+                        .map(|binge_group| (&streams_item.transport_url, binge_group))
+                })
+                .and_then(|(transport_url, binge_group)| {
+                    streams
+                        .iter()
+                        .filter_map(|streams| match streams {
+                            ResourceLoadable {
+                                request,
+                                content: Loadable::Ready(streams),
+                            } => Some((request, streams)),
+                            _ => None,
+                        })
+                        .sorted_by_key(|(request, _)| request.base != *transport_url)
+                        .flat_map(|(_, streams)| streams)
+                        .find(|stream| {
+                            stream.behavior_hints.binge_group.as_ref() == Some(binge_group)
+                        })
+                        .cloned()
+                }),
+        },
+        _ => None,
+    };
+    eq_update(suggested_stream, next_suggested_stream)
+}
+
+fn seasons_selectable_update<E: Env>(
+    seasons_selectable: &mut SeasonsSelectable,
+    season: Option<u32>,
+    selected: &Option<Selected>,
+    meta_items: &[ResourceLoadable<MetaItem>],
+    library: &LibraryBucket,
+) -> Effects {
+    let meta_item = meta_items.iter().find_map(|meta_item| match meta_item {
+        ResourceLoadable {
+            content: Loadable::Ready(meta_item),
+            ..
+        } => Some(meta_item),
+        _ => None,
+    });
+    let library_item = selected
+        .as_ref()
+        .and_then(|selected| library.items.get(&selected.meta_path.id));
+    let seasons = match meta_item {
+        Some(meta_item) => {
+            let watched = library_item
+                .map(|library_item| library_item.state.watched_bitfield(&meta_item.videos));
+            meta_item
+                .videos
+                .iter()
+                .filter_map(|video| {
+                    video
+                        .series_info
+                        .as_ref()
+                        .map(|series_info| (series_info.season, series_info.episode, video))
+                })
+                .sorted_by_key(|(season, episode, _)| (*season == 0, *season, *episode))
+                .group_by(|(season, ..)| *season)
+                .into_iter()
+                .map(|(season, videos)| {
+                    (
+                        season,
+                        videos
+                            .map(|(.., video)| SelectableVideo {
+                                video: video.to_owned(),
+                                status: video_status::<E>(video, library_item, watched.as_ref()),
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        }
+        _ => vec![],
+    };
+    let is_season_available =
+        |selected_season: &u32| seasons.iter().any(|(season, _)| season == selected_season);
+    let selected_season = season
+        .filter(is_season_available)
+        .or_else(|| {
+            seasons_selectable
+                .seasons
+                .iter()
+                .find(|selectable_season| selectable_season.selected)
+                .map(|selectable_season| selectable_season.season)
+                .filter(is_season_available)
+        })
+        .or_else(|| {
+            library_item
+                .and_then(|library_item| library_item.state.video_id.as_ref())
+                .and_then(|video_id| {
+                    seasons.iter().find_map(|(season, videos)| {
+                        videos
+                            .iter()
+                            .any(|selectable_video| selectable_video.video.id == *video_id)
+                            .then_some(*season)
+                    })
+                })
+        })
+        .or_else(|| seasons.first().map(|(season, _)| *season));
+    let next_seasons_selectable = SeasonsSelectable {
+        seasons: seasons
+            .into_iter()
+            .map(|(season, videos)| SelectableSeason {
+                season,
+                selected: selected_season == Some(season),
+                videos,
+            })
+            .collect(),
+    };
+    eq_update(seasons_selectable, next_seasons_selectable)
+}
+
+fn last_vid_released_update<E: Env>(
+    selected: &Option<Selected>,
+    meta_items: &[ResourceLoadable<MetaItem>],
+    library: &LibraryBucket,
+) -> Effects {
+    let library_item = selected
+        .as_ref()
+        .and_then(|selected| library.items.get(&selected.meta_path.id))
+        .filter(|library_item| !library_item.removed);
+    let last_vid_released = meta_items
+        .iter()
+        .find_map(|meta_item| match meta_item {
+            ResourceLoadable {
+                content: Loadable::Ready(meta_item),
+                ..
+            } => Some(meta_item),
+            _ => None,
+        })
+        .and_then(|meta_item| {
+            meta_item
+                .videos
+                .iter()
+                .filter_map(|video| video.released)
+                .filter(|released| *released <= E::now())
+                .max()
+        });
+    match (library_item, last_vid_released) {
+        (Some(library_item), Some(last_vid_released))
+            if library_item.state.last_vid_released < Some(last_vid_released) =>
+        {
+            let mut library_item = library_item.to_owned();
+            library_item.state.last_vid_released = Some(last_vid_released);
+            Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item))).unchanged()
+        }
+        _ => Effects::none().unchanged(),
+    }
+}
+
+fn video_status<E: Env>(
+    video: &Video,
+    library_item: Option<&LibraryItem>,
+    watched: Option<&WatchedBitField>,
+) -> VideoStatus {
+    let is_current = matches!(
+        library_item,
+        Some(library_item) if library_item.state.video_id.as_ref() == Some(&video.id)
+    );
+    match library_item {
+        Some(library_item) if is_current && library_item.state.flagged_watched > 0 => {
+            VideoStatus::Watched
+        }
+        Some(library_item) if is_current && library_item.state.time_offset > 0 => {
+            VideoStatus::InProgress
+        }
+        _ if matches!(watched, Some(watched) if watched.get_video(&video.id)) => {
+            VideoStatus::Watched
+        }
+        _ if matches!(video.released, Some(released) if released > E::now()) => {
+            VideoStatus::Upcoming
+        }
+        _ => VideoStatus::Unwatched,
+    }
+}
@@ -10,4 +10,5 @@ pub mod library_with_filters;
 pub mod meta_details;
 pub mod notifications;
 pub mod player;
+pub mod search;
 pub mod streaming_server;
@@ -1,13 +1,20 @@
-use crate::models::common::{resources_update, Loadable, ResourceLoadable, ResourcesAction};
+use crate::constants::{NOTIFICATIONS_REFRESH_INTERVAL, NOTIFICATION_ITEMS_COUNT};
+use crate::models::common::{
+    eq_update, resources_update, Loadable, ResourceLoadable, ResourcesAction,
+};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::Internal::*;
 use crate::runtime::msg::*;
 use crate::runtime::*;
 use crate::types::addon::{ExtraValue, ResourcePath, ResourceRequest};
-use crate::types::resource::MetaItem;
+use crate::types::library::LibraryBucket;
+use crate::types::resource::{MetaItem, Video};
+use chrono::{DateTime, Utc};
+use derivative::Derivative;
 use futures::FutureExt;
 use lazysort::SortedBy;
 use serde::*;
+use std::collections::{HashMap, HashSet};
 
 // Cinemeta/Channels are curently limited to that many
 // but in general, it's healthy to have some sort of a limit
@@ -15,94 +22,79 @@ const MAX_PER_REQUEST: usize = 50;
 // The name of the extra property
 const LAST_VID_IDS: &str = "lastVideosIds";
 
-#[derive(Default, Serialize)]
+#[derive(PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationItem {
+    pub meta_id: String,
+    pub meta_name: String,
+    pub meta_poster: Option<String>,
+    pub video: Video,
+}
+
+#[derive(Derivative, Serialize)]
+#[derivative(Default)]
+#[serde(rename_all = "camelCase")]
 pub struct Notifications {
     pub groups: Vec<ResourceLoadable<Vec<MetaItem>>>,
+    pub notification_items: Vec<NotificationItem>,
+    #[serde(skip)]
+    pub last_refreshed: Option<DateTime<Utc>>,
+    /// The maximum number of notification items
+    #[serde(skip)]
+    #[derivative(Default(value = "NOTIFICATION_ITEMS_COUNT"))]
+    pub items_count: usize,
+}
+
+impl Notifications {
+    pub fn new(items_count: usize) -> Self {
+        Notifications {
+            items_count,
+            ..Default::default()
+        }
+    }
 }
 impl<E: Env + 'static> UpdateWithCtx<E> for Notifications {
     fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
         match msg {
             Msg::Action(Action::Load(ActionLoad::Notifications)) => {
-                let library = &ctx.library;
-
-                let (groups, effects): (Vec<_>, Vec<_>) = ctx
-                    .profile
-                    .addons
-                    .iter()
-                    .flat_map(|addon| {
-                        // The catalog supports this property
-                        let viable_catalogs = addon
-                            .manifest
-                            .catalogs
-                            .iter()
-                            .filter(|cat| cat.extra.iter().any(|e| e.name == LAST_VID_IDS));
-
-                        viable_catalogs.flat_map(move |cat| {
-                            let relevant_items = library
-                                .items
-                                .values()
-                                // The item must be eligible for notifications,
-                                // but also meta about it must be provided by the given add-on
-                                .filter(|item| {
-                                    !item.state.no_notif
-                                        && !item.removed
-                                        && cat.r#type == item.r#type
-                                        && addon.manifest.is_resource_supported(
-                                            &ResourcePath::without_extra(
-                                                "meta",
-                                                &item.r#type,
-                                                &item.id,
-                                            ),
-                                        )
-                                })
-                                .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
-                                .collect::<Vec<_>>();
-
-                            // .chunks will also make sure that if relevant_items is empty,
-                            // we get no chunks (so no group)
-                            relevant_items
-                                .chunks(MAX_PER_REQUEST)
-                                .map(|items_page| -> (_, Effect) {
-                                    let ids =
-                                        items_page.iter().map(|x| x.id.clone()).collect::<Vec<_>>();
-                                    let extra_props = [ExtraValue {
-                                        name: LAST_VID_IDS.into(),
-                                        value: ids.join(","),
-                                    }];
-                                    let path = ResourcePath::with_extra(
-                                        "catalog",
-                                        &cat.r#type,
-                                        &cat.id,
-                                        &extra_props,
-                                    );
-                                    let addon_req =
-                                        ResourceRequest::new(addon.transport_url.to_owned(), path);
-
-                                    (
-                                        ResourceLoadable {
-                                            request: addon_req.to_owned(),
-                                            content: Loadable::Loading,
-                                        },
-                                        E::addon_transport(&addon_req.base)
-                                            .resource(&addon_req.path)
-                                            .map(move |result| {
-                                                Msg::Internal(Internal::ResourceRequestResult(
-                                                    addon_req,
-                                                    Box::new(result),
-                                                ))
-                                            })
-                                            .boxed_local()
-                                            .into(),
-                                    )
-                                })
-                                .collect::<Vec<_>>()
-                        })
-                    })
-                    .unzip();
-
-                self.groups = groups;
-                Effects::many(effects)
+                let groups_effects = groups_update::<E>(&mut self.groups, ctx);
+                self.last_refreshed = Some(E::now());
+                let notification_items_effects = notification_items_update(
+                    &mut self.notification_items,
+                    &self.groups,
+                    &ctx.library,
+                    self.items_count,
+                );
+                groups_effects.join(notification_items_effects)
+            }
+            Msg::Internal(Tick) => match self.last_refreshed {
+                Some(last_refreshed)
+                    if E::now() - last_refreshed >= *NOTIFICATIONS_REFRESH_INTERVAL =>
+                {
+                    let groups_effects = groups_update::<E>(&mut self.groups, ctx);
+                    self.last_refreshed = Some(E::now());
+                    let notification_items_effects = notification_items_update(
+                        &mut self.notification_items,
+                        &self.groups,
+                        &ctx.library,
+                        self.items_count,
+                    );
+                    groups_effects.join(notification_items_effects)
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Action(Action::Notifications(ActionNotifications::Dismiss(id))) => {
+                dismiss_notifications(&self.groups, Some(id), &ctx.library)
+            }
+            Msg::Action(Action::Notifications(ActionNotifications::DismissAll)) => {
+                dismiss_notifications(&self.groups, None, &ctx.library)
             }
+            Msg::Internal(LibraryChanged(_)) => notification_items_update(
+                &mut self.notification_items,
+                &self.groups,
+                &ctx.library,
+                self.items_count,
+            ),
             Msg::Internal(ResourceRequestResult(req, result)) => {
                 if let Some(idx) = self.groups.iter().position(|g| g.request == *req) {
                     resources_update::<E, _>(
@@ -134,7 +126,13 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Notifications {
                             }
                         }
                     }
-                    Effects::none()
+                    let notification_items_effects = notification_items_update(
+                        &mut self.notification_items,
+                        &self.groups,
+                        &ctx.library,
+                        self.items_count,
+                    );
+                    Effects::none().join(notification_items_effects)
                 } else {
                     Effects::none().unchanged()
                 }
@@ -143,3 +141,172 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Notifications {
         }
     }
 }
+
+fn groups_update<E: Env + 'static>(
+    groups: &mut Vec<ResourceLoadable<Vec<MetaItem>>>,
+    ctx: &Ctx,
+) -> Effects {
+    let library = &ctx.library;
+
+    let (next_groups, effects): (Vec<_>, Vec<_>) = ctx
+        .profile
+        .addons
+        .iter()
+        .flat_map(|addon| {
+            // The catalog supports this property
+            let viable_catalogs = addon
+                .manifest
+                .catalogs
+                .iter()
+                .filter(|cat| cat.extra.iter().any(|e| e.name == LAST_VID_IDS));
+
+            viable_catalogs.flat_map(move |cat| {
+                let relevant_items =
+                    library
+                        .items
+                        .values()
+                        // The item must be eligible for notifications,
+                        // but also meta about it must be provided by the given add-on
+                        .filter(|item| {
+                            !item.state.no_notif
+                                && !item.removed
+                                && cat.r#type == item.r#type
+                                && addon.manifest.is_resource_supported(
+                                    &ResourcePath::without_extra("meta", &item.r#type, &item.id),
+                                )
+                        })
+                        .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
+                        .collect::<Vec<_>>();
+
+                // .chunks will also make sure that if relevant_items is empty,
+                // we get no chunks (so no group)
+                relevant_items
+                    .chunks(MAX_PER_REQUEST)
+                    .map(|items_page| -> (_, Effect) {
+                        let ids = items_page.iter().map(|x| x.id.clone()).collect::<Vec<_>>();
+                        let extra_props = [ExtraValue {
+                            name: LAST_VID_IDS.into(),
+                            value: ids.join(","),
+                        }];
+                        let path =
+                            ResourcePath::with_extra("catalog", &cat.r#type, &cat.id, &extra_props);
+                        let addon_req = ResourceRequest::new(addon.transport_url.to_owned(), path);
+
+                        (
+                            ResourceLoadable {
+                                request: addon_req.to_owned(),
+                                content: Loadable::Loading,
+                            },
+                            E::addon_transport(&addon_req.base)
+                                .resource(&addon_req.path)
+                                .map(move |result| {
+                                    Msg::Internal(Internal::ResourceRequestResult(
+                                        addon_req,
+                                        Box::new(result),
+                                    ))
+                                })
+                                .boxed_local()
+                                .into(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .unzip();
+
+    *groups = next_groups;
+    Effects::many(effects)
+}
+
+// Moves `last_vid_released` forward to the newest notified video,
+// so the dismissed videos are not notifications anymore once the library changes
+fn dismiss_notifications(
+    groups: &[ResourceLoadable<Vec<MetaItem>>],
+    id: Option<&String>,
+    library: &LibraryBucket,
+) -> Effects {
+    groups
+        .iter()
+        .filter_map(|group| group.content.as_ref().ready())
+        .flatten()
+        .filter(|meta_item| match id {
+            Some(id) => meta_item.id == *id,
+            None => true,
+        })
+        .filter_map(|meta_item| {
+            meta_item
+                .videos
+                .iter()
+                .filter_map(|video| video.released)
+                .max()
+                .map(|released| (&meta_item.id, released))
+        })
+        .fold(
+            HashMap::<&String, DateTime<Utc>>::new(),
+            |mut last_vid_released, (id, released)| {
+                let lvr = last_vid_released.entry(id).or_insert(released);
+                *lvr = (*lvr).max(released);
+                last_vid_released
+            },
+        )
+        .into_iter()
+        .filter_map(|(id, released)| {
+            library
+                .items
+                .get(id)
+                .filter(|library_item| library_item.state.last_vid_released < Some(released))
+                .map(|library_item| {
+                    let mut library_item = library_item.to_owned();
+                    library_item.state.last_vid_released = Some(released);
+                    Effects::msg(Msg::Internal(Internal::UpdateLibraryItem(library_item)))
+                        .unchanged()
+                })
+        })
+        .fold(Effects::none().unchanged(), |effects, item_effects| {
+            effects.join(item_effects)
+        })
+}
+
+fn notification_items_update(
+    notification_items: &mut Vec<NotificationItem>,
+    groups: &[ResourceLoadable<Vec<MetaItem>>],
+    library: &LibraryBucket,
+    items_count: usize,
+) -> Effects {
+    let mut seen = HashSet::new();
+    let next_notification_items = groups
+        .iter()
+        .filter_map(|group| match &group.content {
+            Loadable::Ready(meta_items) => Some(meta_items),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|meta_item| {
+            library
+                .items
+                .get(&meta_item.id)
+                .filter(|library_item| !library_item.state.no_notif && !library_item.removed)
+                .and_then(|library_item| library_item.state.last_vid_released)
+                .map(|last_vid_released| (meta_item, last_vid_released))
+        })
+        .flat_map(|(meta_item, last_vid_released)| {
+            meta_item
+                .videos
+                .iter()
+                .filter(move |video| {
+                    matches!(video.released, Some(released) if released > last_vid_released)
+                })
+                .map(move |video| NotificationItem {
+                    meta_id: meta_item.id.to_owned(),
+                    meta_name: meta_item.name.to_owned(),
+                    meta_poster: meta_item.poster.to_owned(),
+                    video: video.to_owned(),
+                })
+        })
+        // the same meta item may be provided by multiple add-ons
+        .filter(|item| seen.insert((item.meta_id.to_owned(), item.video.id.to_owned())))
+        .sorted_by(|a, b| b.video.released.cmp(&a.video.released))
+        .take(items_count)
+        .collect::<Vec<_>>();
+    eq_update(notification_items, next_notification_items)
+}
@@ -1,7 +1,8 @@
 use crate::constants::WATCHED_THRESHOLD_COEF;
 use crate::models::common::{
-    eq_update, resource_update, resources_update_with_vector_content, Loadable, ResourceAction,
-    ResourceLoadable, ResourcesAction,
+    eq_update, resource_update, resource_update_with_vector_content,
+    resources_update_with_vector_content, Loadable, ResourceAction, ResourceLoadable,
+    ResourcesAction,
 };
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionLoad, ActionPlayer, Internal, Msg};
@@ -11,7 +12,9 @@ use crate::types::library::{
     LibraryBucket, LibraryItem, LibraryItemBehaviorHints, LibraryItemState,
 };
 use crate::types::profile::Settings as ProfileSettings;
-use crate::types::resource::{MetaItem, Stream, Subtitles, Video};
+use crate::types::resource::{MetaItem, Stream, StreamBehaviorHints, Subtitles, Video};
+use crate::types::streams::StreamsItem;
+use crate::types::subtitles::{SubtitlesBucket, SubtitlesItem};
 use serde::{Deserialize, Serialize};
 use std::cmp;
 
@@ -31,13 +34,34 @@ pub struct Player {
     pub meta_item: Option<ResourceLoadable<MetaItem>>,
     pub subtitles: Vec<ResourceLoadable<Vec<Subtitles>>>,
     pub next_video: Option<Video>,
+    pub next_streams: Option<ResourceLoadable<Vec<Stream>>>,
+    pub next_stream: Option<Stream>,
     pub library_item: Option<LibraryItem>,
+    pub subtitles_item: Option<SubtitlesItem>,
 }
 
 impl<E: Env + 'static> UpdateWithCtx<E> for Player {
     fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
         match msg {
             Msg::Action(Action::Load(ActionLoad::Player(selected))) => {
+                let streams_item_effects = match selected {
+                    Selected {
+                        stream,
+                        stream_request: Some(stream_request),
+                        meta_request: Some(meta_request),
+                        ..
+                    } if self.selected.as_ref() != Some(selected) => {
+                        Effects::msg(Msg::Internal(Internal::UpdateStreamsItem(StreamsItem {
+                            meta_id: meta_request.path.id.to_owned(),
+                            video_id: stream_request.path.id.to_owned(),
+                            stream: stream.to_owned(),
+                            transport_url: stream_request.base.to_owned(),
+                            mtime: E::now(),
+                        })))
+                        .unchanged()
+                    }
+                    _ => Effects::none().unchanged(),
+                };
                 let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
                 let meta_item_effects = match &selected.meta_request {
                     Some(meta_request) => resource_update::<E, _>(
@@ -64,26 +88,45 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.meta_item,
                     &ctx.profile.settings,
                 );
+                let next_streams_effects = next_streams_update::<E>(
+                    &mut self.next_streams,
+                    &self.next_video,
+                    &self.selected,
+                );
+                let next_stream_effects =
+                    next_stream_update(&mut self.next_stream, &self.next_streams, &self.selected);
                 let library_item_effects =
                     library_item_update::<E>(&mut self.library_item, &self.meta_item, &ctx.library);
+                let subtitles_item_effects =
+                    subtitles_item_update(&mut self.subtitles_item, &self.selected, &ctx.subtitles);
                 selected_effects
                     .join(meta_item_effects)
                     .join(subtitles_effects)
                     .join(next_video_effects)
+                    .join(next_streams_effects)
+                    .join(next_stream_effects)
                     .join(library_item_effects)
+                    .join(subtitles_item_effects)
+                    .join(streams_item_effects)
             }
             Msg::Action(Action::Unload) => {
                 let selected_effects = eq_update(&mut self.selected, None);
                 let meta_item_effects = eq_update(&mut self.meta_item, None);
                 let subtitles_effects = eq_update(&mut self.subtitles, vec![]);
                 let next_video_effects = eq_update(&mut self.next_video, None);
+                let next_streams_effects = eq_update(&mut self.next_streams, None);
+                let next_stream_effects = eq_update(&mut self.next_stream, None);
                 let library_item_effects =
                     library_item_update::<E>(&mut self.library_item, &self.meta_item, &ctx.library);
+                let subtitles_item_effects = eq_update(&mut self.subtitles_item, None);
                 selected_effects
                     .join(meta_item_effects)
                     .join(subtitles_effects)
                     .join(next_video_effects)
+                    .join(next_streams_effects)
+                    .join(next_stream_effects)
                     .join(library_item_effects)
+                    .join(subtitles_item_effects)
             }
             Msg::Action(Action::Player(ActionPlayer::UpdateLibraryItemState {
                 time,
@@ -146,6 +189,33 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                 .unchanged(),
                 _ => Effects::none().unchanged(),
             },
+            Msg::Action(Action::Player(ActionPlayer::SelectSubtitles {
+                id,
+                lang,
+                delay,
+                size,
+            })) => match &self.selected {
+                Some(Selected {
+                    stream_request: Some(stream_request),
+                    meta_request: Some(meta_request),
+                    ..
+                }) => Effects::msg(Msg::Internal(Internal::UpdateSubtitlesItem(
+                    SubtitlesItem {
+                        library_item_id: meta_request.path.id.to_owned(),
+                        video_id: stream_request.path.id.to_owned(),
+                        subtitles_id: id.to_owned(),
+                        subtitles_lang: lang.to_owned(),
+                        delay: delay.to_owned(),
+                        size: size.to_owned(),
+                        mtime: E::now(),
+                    },
+                )))
+                .unchanged(),
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::SubtitlesChanged) => {
+                subtitles_item_update(&mut self.subtitles_item, &self.selected, &ctx.subtitles)
+            }
             Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
                 let meta_item_effects = resource_update::<E, _>(
                     &mut self.meta_item,
@@ -169,11 +239,29 @@ impl<E: Env + 'static> UpdateWithCtx<E> for Player {
                     &self.meta_item,
                     &ctx.profile.settings,
                 );
+                let next_streams_result_effects = resource_update_with_vector_content::<E, _>(
+                    &mut self.next_streams,
+                    ResourceAction::ResourceRequestResult {
+                        request,
+                        result,
+                        limit: &None,
+                    },
+                );
+                let next_streams_effects = next_streams_update::<E>(
+                    &mut self.next_streams,
+                    &self.next_video,
+                    &self.selected,
+                );
+                let next_stream_effects =
+                    next_stream_update(&mut self.next_stream, &self.next_streams, &self.selected);
                 let library_item_effects =
                     library_item_update::<E>(&mut self.library_item, &self.meta_item, &ctx.library);
                 meta_item_effects
                     .join(subtitles_effects)
                     .join(next_video_effects)
+                    .join(next_streams_result_effects)
+                    .join(next_streams_effects)
+                    .join(next_stream_effects)
                     .join(library_item_effects)
             }
             _ => Effects::none().unchanged(),
@@ -212,6 +300,104 @@ fn next_video_update(
     eq_update(video, next_video)
 }
 
+fn next_streams_update<E: Env + 'static>(
+    next_streams: &mut Option<ResourceLoadable<Vec<Stream>>>,
+    next_video: &Option<Video>,
+    selected: &Option<Selected>,
+) -> Effects {
+    match (next_video, selected) {
+        (
+            Some(next_video),
+            Some(Selected {
+                stream:
+                    Stream {
+                        behavior_hints:
+                            StreamBehaviorHints {
+                                binge_group: Some(_),
+                                ..
+                            },
+                        ..
+                    },
+                stream_request: Some(stream_request),
+                ..
+            }),
+        ) => {
+            let request = ResourceRequest {
+                base: stream_request.base.to_owned(),
+                path: ResourcePath {
+                    id: next_video.id.to_owned(),
+                    ..stream_request.path.to_owned()
+                },
+            };
+            if next_video.streams.is_empty() {
+                resource_update::<E, _>(
+                    next_streams,
+                    ResourceAction::ResourceRequested { request: &request },
+                )
+            } else {
+                eq_update(
+                    next_streams,
+                    Some(ResourceLoadable {
+                        request,
+                        content: Loadable::Ready(next_video.streams.to_owned()),
+                    }),
+                )
+            }
+        }
+        _ => eq_update(next_streams, None),
+    }
+}
+
+fn next_stream_update(
+    next_stream: &mut Option<Stream>,
+    next_streams: &Option<ResourceLoadable<Vec<Stream>>>,
+    selected: &Option<Selected>,
+) -> Effects {
+    let next_next_stream = match (next_streams, selected) {
+        (
+            Some(ResourceLoadable {
+                content: Loadable::Ready(next_streams),
+                ..
+            }),
+            Some(Selected {
+                stream:
+                    Stream {
+                        behavior_hints:
+                            StreamBehaviorHints {
+                                binge_group: Some(binge_group),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            }),
+        ) => next_streams
+            .iter()
+            .find(|stream| stream.behavior_hints.binge_group.as_ref() == Some(binge_group))
+            .cloned(),
+        _ => None,
+    };
+    eq_update(next_stream, next_next_stream)
+}
+
+fn subtitles_item_update(
+    subtitles_item: &mut Option<SubtitlesItem>,
+    selected: &Option<Selected>,
+    subtitles: &SubtitlesBucket,
+) -> Effects {
+    let next_subtitles_item = match selected {
+        Some(Selected {
+            stream_request: Some(stream_request),
+            meta_request: Some(meta_request),
+            ..
+        }) => subtitles
+            .get(&meta_request.path.id, &stream_request.path.id)
+            .cloned(),
+        _ => None,
+    };
+    eq_update(subtitles_item, next_subtitles_item)
+}
+
 fn library_item_update<E: Env>(
     library_item: &mut Option<LibraryItem>,
     meta_item: &Option<ResourceLoadable<MetaItem>>,
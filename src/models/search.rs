@@ -0,0 +1,191 @@
+use crate::constants::{
+    CATALOG_PAGE_SIZE, SEARCH_DEBOUNCE_DELAY, SEARCH_EXTRA_NAME, SEARCH_HISTORY_RECENT_COUNT,
+    TYPE_PRIORITIES,
+};
+use crate::models::common::{
+    compare_with_priorities, eq_update, resources_update_with_vector_content, Loadable,
+    ResourceLoadable, ResourcesAction,
+};
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, UpdateWithCtx};
+use crate::types::addon::{AggrRequest, ExtraValue};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItemPreview;
+use crate::types::search_history::SearchHistoryBucket;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Selected {
+    pub query: String,
+}
+
+#[derive(Default, Serialize)]
+pub struct Search {
+    pub selected: Option<Selected>,
+    #[serde(skip)]
+    pub catalogs: Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    pub results: Vec<MetaItemPreview>,
+    pub loading: bool,
+    pub history: Vec<String>,
+}
+
+impl<E: Env + 'static> UpdateWithCtx<E> for Search {
+    fn update(&mut self, msg: &Msg, ctx: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::Search(selected))) => {
+                if self.selected.as_ref() == Some(selected) {
+                    return Effects::none().unchanged();
+                };
+                let selected_effects = eq_update(&mut self.selected, Some(selected.to_owned()));
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let loading_effects = eq_update(&mut self.loading, !selected.query.is_empty());
+                let results_effects = results_update(&mut self.results, &self.catalogs);
+                let history_effects = history_update(&mut self.history, &ctx.search_history);
+                let debounce_effects = if selected.query.is_empty() {
+                    Effects::none().unchanged()
+                } else {
+                    let query = selected.query.to_owned();
+                    Effects::future(
+                        E::sleep(*SEARCH_DEBOUNCE_DELAY)
+                            .map(move |_| Msg::Internal(Internal::SearchDebounced(query)))
+                            .boxed_local(),
+                    )
+                    .unchanged()
+                };
+                selected_effects
+                    .join(catalogs_effects)
+                    .join(loading_effects)
+                    .join(results_effects)
+                    .join(history_effects)
+                    .join(debounce_effects)
+            }
+            Msg::Action(Action::Unload) => {
+                let selected_effects = eq_update(&mut self.selected, None);
+                let catalogs_effects = eq_update(&mut self.catalogs, vec![]);
+                let results_effects = eq_update(&mut self.results, vec![]);
+                let loading_effects = eq_update(&mut self.loading, false);
+                let history_effects = eq_update(&mut self.history, vec![]);
+                selected_effects
+                    .join(catalogs_effects)
+                    .join(results_effects)
+                    .join(loading_effects)
+                    .join(history_effects)
+            }
+            Msg::Internal(Internal::SearchDebounced(query)) => match &self.selected {
+                Some(selected) if selected.query == *query => {
+                    let catalogs_effects =
+                        catalogs_update::<E>(&mut self.catalogs, query, &ctx.profile);
+                    let loading_effects = loading_update(&mut self.loading, &self.catalogs);
+                    catalogs_effects.join(loading_effects).join(
+                        Effects::msg(Msg::Internal(Internal::UpdateSearchHistory(
+                            query.to_owned(),
+                        )))
+                        .unchanged(),
+                    )
+                }
+                _ => Effects::none().unchanged(),
+            },
+            Msg::Internal(Internal::ResourceRequestResult(request, result)) => {
+                let catalogs_effects = resources_update_with_vector_content::<E, _>(
+                    &mut self.catalogs,
+                    ResourcesAction::ResourceRequestResult {
+                        request,
+                        result,
+                        limit: &Some(CATALOG_PAGE_SIZE),
+                    },
+                );
+                if catalogs_effects.has_changed {
+                    let results_effects = results_update(&mut self.results, &self.catalogs);
+                    let loading_effects = loading_update(&mut self.loading, &self.catalogs);
+                    catalogs_effects.join(results_effects).join(loading_effects)
+                } else {
+                    catalogs_effects
+                }
+            }
+            Msg::Internal(Internal::SearchHistoryChanged) if self.selected.is_some() => {
+                history_update(&mut self.history, &ctx.search_history)
+            }
+            Msg::Internal(Internal::ProfileChanged) => match &self.selected {
+                Some(selected) if !selected.query.is_empty() && !self.catalogs.is_empty() => {
+                    let catalogs_effects =
+                        catalogs_update::<E>(&mut self.catalogs, &selected.query, &ctx.profile);
+                    let results_effects = results_update(&mut self.results, &self.catalogs);
+                    let loading_effects = loading_update(&mut self.loading, &self.catalogs);
+                    catalogs_effects.join(results_effects).join(loading_effects)
+                }
+                _ => Effects::none().unchanged(),
+            },
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+fn catalogs_update<E: Env + 'static>(
+    catalogs: &mut Vec<ResourceLoadable<Vec<MetaItemPreview>>>,
+    query: &str,
+    profile: &Profile,
+) -> Effects {
+    resources_update_with_vector_content::<E, _>(
+        catalogs,
+        ResourcesAction::ResourcesRequested {
+            request: &AggrRequest::AllCatalogs {
+                extra: &vec![ExtraValue {
+                    name: SEARCH_EXTRA_NAME.to_owned(),
+                    value: query.to_owned(),
+                }],
+            },
+            addons: &profile.addons,
+        },
+    )
+}
+
+fn history_update(history: &mut Vec<String>, search_history: &SearchHistoryBucket) -> Effects {
+    let next_history = search_history
+        .recent()
+        .take(SEARCH_HISTORY_RECENT_COUNT)
+        .cloned()
+        .collect::<Vec<_>>();
+    eq_update(history, next_history)
+}
+
+fn loading_update(
+    loading: &mut bool,
+    catalogs: &[ResourceLoadable<Vec<MetaItemPreview>>],
+) -> Effects {
+    let next_loading = catalogs.iter().any(|catalog| catalog.content.is_loading());
+    eq_update(loading, next_loading)
+}
+
+fn results_update(
+    results: &mut Vec<MetaItemPreview>,
+    catalogs: &[ResourceLoadable<Vec<MetaItemPreview>>],
+) -> Effects {
+    let mut ids = HashSet::new();
+    let mut next_results = catalogs
+        .iter()
+        .filter_map(|catalog| match &catalog.content {
+            Loadable::Ready(content) => Some(content),
+            _ => None,
+        })
+        .flatten()
+        .filter(|meta_item| ids.insert(&meta_item.id))
+        .cloned()
+        .collect::<Vec<_>>();
+    next_results.sort_by(|a, b| {
+        compare_with_priorities(b.r#type.as_str(), a.r#type.as_str(), &*TYPE_PRIORITIES).then_with(
+            || match (a.popularity, b.popularity) {
+                (Some(a_popularity), Some(b_popularity)) => b_popularity
+                    .partial_cmp(&a_popularity)
+                    .unwrap_or(Ordering::Equal),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+        )
+    });
+    eq_update(results, next_results)
+}
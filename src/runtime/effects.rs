@@ -48,6 +48,11 @@ impl Effects {
     pub fn futures(futures: Vec<EffectFuture>) -> Self {
         Effects::many(futures.into_iter().map(Effect::from).collect())
     }
+    pub fn has_futures(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Future(_)))
+    }
     pub fn unchanged(mut self) -> Self {
         self.has_changed = false;
         self
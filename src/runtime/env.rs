@@ -1,13 +1,16 @@
-use crate::addon_transport::{AddonHTTPTransport, AddonTransport, UnsupportedTransport};
+use crate::addon_transport::{
+    AddonCachedTransport, AddonHTTPTransport, AddonTransport, UnsupportedTransport,
+};
 use crate::constants::{
     LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY, PROFILE_STORAGE_KEY, SCHEMA_VERSION,
     SCHEMA_VERSION_STORAGE_KEY,
 };
 use crate::models::ctx::Ctx;
 use crate::models::streaming_server::StreamingServer;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::future::LocalBoxFuture;
-use futures::{future, Future, FutureExt, TryFutureExt};
+use futures::stream::LocalBoxStream;
+use futures::{future, stream, Future, FutureExt, StreamExt, TryFutureExt};
 use http::Request;
 use serde::ser::SerializeStruct;
 use serde::{Deserialize, Serialize, Serializer};
@@ -75,6 +78,8 @@ pub type EnvFuture<T> = LocalBoxFuture<'static, T>;
 
 pub type TryEnvFuture<T> = EnvFuture<Result<T, EnvError>>;
 
+pub type EnvStream<T> = LocalBoxStream<'static, T>;
+
 pub trait Env {
     fn fetch<IN, OUT>(request: Request<IN>) -> TryEnvFuture<OUT>
     where
@@ -88,16 +93,29 @@ pub trait Env {
     where
         F: Future<Output = ()> + 'static;
     fn now() -> DateTime<Utc>;
+    fn sleep(duration: Duration) -> EnvFuture<()>;
+    fn interval(duration: Duration) -> EnvStream<()>
+    where
+        Self: Sized + 'static,
+    {
+        stream::unfold((), move |_| Self::sleep(duration).map(|_| Some(((), ())))).boxed_local()
+    }
     fn flush_analytics() -> EnvFuture<()>;
     fn analytics_context(ctx: &Ctx, streaming_server: &StreamingServer) -> serde_json::Value;
     #[cfg(debug_assertions)]
     fn log(message: String);
+    // Resources over http(s) are cached in memory following the cacheMaxAge, staleRevalidate
+    // and staleError hints of the response body. Cache-Control headers are not honoured
+    // as fetch exposes the body only. The cache is cleared whenever the profile changes.
     fn addon_transport(transport_url: &Url) -> Box<dyn AddonTransport>
     where
         Self: Sized + 'static,
     {
         match transport_url.scheme() {
-            "http" | "https" => Box::new(AddonHTTPTransport::<Self>::new(transport_url.to_owned())),
+            "http" | "https" => Box::new(AddonCachedTransport::<Self>::new(
+                transport_url.to_owned(),
+                Box::new(AddonHTTPTransport::<Self>::new(transport_url.to_owned())),
+            )),
             _ => Box::new(UnsupportedTransport::new(transport_url.to_owned())),
         }
     }
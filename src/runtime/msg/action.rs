@@ -5,11 +5,13 @@ use crate::models::installed_addons_with_filters::Selected as InstalledAddonsWit
 use crate::models::library_with_filters::Selected as LibraryWithFiltersSelected;
 use crate::models::meta_details::Selected as MetaDetailsSelected;
 use crate::models::player::Selected as PlayerSelected;
+use crate::models::search::Selected as SearchSelected;
 use crate::models::streaming_server::Settings as StreamingServerSettings;
 use crate::types::addon::Descriptor;
 use crate::types::api::AuthRequest;
+use crate::types::library::LibraryExport;
 use crate::types::profile::Settings as ProfileSettings;
-use crate::types::resource::MetaItemPreview;
+use crate::types::resource::{MetaItem, MetaItemPreview};
 use serde::Deserialize;
 
 #[derive(Clone, Deserialize)]
@@ -23,11 +25,60 @@ pub enum ActionCtx {
     AddToLibrary(MetaItemPreview),
     RemoveFromLibrary(String),
     RewindLibraryItem(String),
+    RestoreLibraryItem(String),
+    PurgeLibraryItems,
+    ExportLibrary,
+    ImportLibrary(LibraryExport),
+    SetLibraryItemNotifications {
+        id: String,
+        no_notif: bool,
+    },
+    MarkAsWatched {
+        id: String,
+        watched: bool,
+    },
+    MarkVideoAsWatched {
+        meta_item: MetaItem,
+        video_id: String,
+        watched: bool,
+    },
+    MarkSeasonAsWatched {
+        meta_item: MetaItem,
+        season: u32,
+        watched: bool,
+    },
     PushUserToAPI,
     PullUserFromAPI,
     PushAddonsToAPI,
     PullAddonsFromAPI,
     SyncLibraryWithAPI,
+    FlushOutbox,
+    ClearSearchHistory,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionCatalogWithFilters {
+    LoadNextPage,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionLibraryWithFilters {
+    ToggleNotifications(String),
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionMetaDetails {
+    SelectSeason(u32),
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "action", content = "args")]
+pub enum ActionNotifications {
+    Dismiss(String),
+    DismissAll,
 }
 
 #[derive(Clone, Deserialize)]
@@ -40,8 +91,17 @@ pub enum ActionStreamingServer {
 #[derive(Clone, Deserialize)]
 #[serde(tag = "action", content = "args")]
 pub enum ActionPlayer {
-    UpdateLibraryItemState { time: u64, duration: u64 },
+    UpdateLibraryItemState {
+        time: u64,
+        duration: u64,
+    },
     PushToLibrary,
+    SelectSubtitles {
+        id: Option<String>,
+        lang: Option<String>,
+        delay: Option<i64>,
+        size: Option<u8>,
+    },
 }
 
 #[derive(Clone, Deserialize)]
@@ -54,6 +114,7 @@ pub enum ActionLoad {
     LibraryWithFilters(LibraryWithFiltersSelected),
     MetaDetails(MetaDetailsSelected),
     Player(PlayerSelected),
+    Search(SearchSelected),
     Notifications,
 }
 
@@ -64,6 +125,10 @@ pub enum ActionLoad {
 #[serde(tag = "action", content = "args")]
 pub enum Action {
     Ctx(ActionCtx),
+    CatalogWithFilters(ActionCatalogWithFilters),
+    LibraryWithFilters(ActionLibraryWithFilters),
+    MetaDetails(ActionMetaDetails),
+    Notifications(ActionNotifications),
     StreamingServer(ActionStreamingServer),
     Player(ActionPlayer),
     Load(ActionLoad),
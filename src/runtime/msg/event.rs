@@ -1,5 +1,6 @@
 use crate::models::ctx::CtxError;
 use crate::types::api::AuthRequest;
+use crate::types::library::LibraryExport;
 use crate::types::profile::{AuthKey, Settings, UID};
 use serde::Serialize;
 use url::Url;
@@ -10,23 +11,117 @@ use url::Url;
 #[derive(Clone, Serialize)]
 #[serde(tag = "event", content = "args")]
 pub enum Event {
-    ProfilePushedToStorage { uid: UID },
-    LibraryItemsPushedToStorage { ids: Vec<String> },
-    UserPulledFromAPI { uid: UID },
-    UserPushedToAPI { uid: UID },
-    AddonsPulledFromAPI { transport_urls: Vec<Url> },
-    AddonsPushedToAPI { transport_urls: Vec<Url> },
-    LibrarySyncWithAPIPlanned { plan: (Vec<String>, Vec<String>) },
-    LibraryItemsPushedToAPI { ids: Vec<String> },
-    LibraryItemsPulledFromAPI { ids: Vec<String> },
-    UserAuthenticated { auth_request: AuthRequest },
-    UserLoggedOut { uid: UID },
-    SessionDeleted { auth_key: AuthKey },
-    AddonInstalled { transport_url: Url, id: String },
-    AddonUninstalled { transport_url: Url, id: String },
-    SettingsUpdated { settings: Settings },
-    LibraryItemAdded { id: String },
-    LibraryItemRemoved { id: String },
-    LibraryItemRewided { id: String },
-    Error { error: CtxError, source: Box<Event> },
+    ProfilePushedToStorage {
+        uid: UID,
+    },
+    LibraryItemsPushedToStorage {
+        ids: Vec<String>,
+    },
+    OutboxPushedToStorage {
+        uid: UID,
+    },
+    SearchHistoryPushedToStorage {
+        uid: UID,
+    },
+    StreamsPushedToStorage {
+        uid: UID,
+    },
+    SubtitlesPushedToStorage {
+        uid: UID,
+    },
+    UserPulledFromAPI {
+        uid: UID,
+    },
+    UserPushedToAPI {
+        uid: UID,
+    },
+    AddonsPulledFromAPI {
+        transport_urls: Vec<Url>,
+    },
+    AddonsPushedToAPI {
+        transport_urls: Vec<Url>,
+    },
+    SettingsPulledFromAPI {
+        settings: Settings,
+    },
+    SettingsPushedToAPI {
+        settings: Settings,
+    },
+    LibrarySyncWithAPIPlanned {
+        plan: (Vec<String>, Vec<String>),
+    },
+    LibrarySyncProgress {
+        pulled: usize,
+        pull_total: usize,
+        pushed: usize,
+        push_total: usize,
+    },
+    LibraryItemsPushedToAPI {
+        ids: Vec<String>,
+    },
+    LibraryItemsPulledFromAPI {
+        ids: Vec<String>,
+    },
+    OutboxFlushed {
+        ids: Vec<String>,
+        transport_urls: Vec<Url>,
+    },
+    UserAuthenticated {
+        auth_request: AuthRequest,
+    },
+    UserLoggedOut {
+        uid: UID,
+    },
+    SessionDeleted {
+        auth_key: AuthKey,
+    },
+    AddonInstalled {
+        transport_url: Url,
+        id: String,
+    },
+    AddonUninstalled {
+        transport_url: Url,
+        id: String,
+    },
+    SettingsUpdated {
+        settings: Settings,
+    },
+    LibraryItemAdded {
+        id: String,
+    },
+    LibraryItemRemoved {
+        id: String,
+    },
+    LibraryItemRewided {
+        id: String,
+    },
+    LibraryItemRestored {
+        id: String,
+    },
+    LibraryItemsPurged {
+        ids: Vec<String>,
+    },
+    LibraryExported {
+        export: LibraryExport,
+    },
+    LibraryImported {
+        added: usize,
+        updated: usize,
+        skipped: usize,
+    },
+    LibraryItemNotificationsToggled {
+        id: String,
+        no_notif: bool,
+    },
+    LibraryItemMarkedAsWatched {
+        id: String,
+        watched: bool,
+    },
+    SearchHistoryCleared {
+        uid: UID,
+    },
+    Error {
+        error: CtxError,
+        source: Box<Event>,
+    },
 }
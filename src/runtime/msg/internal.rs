@@ -2,9 +2,12 @@ use crate::models::ctx::CtxError;
 use crate::models::streaming_server::Settings as StreamingServerSettings;
 use crate::runtime::EnvError;
 use crate::types::addon::{Descriptor, Manifest, ResourceRequest, ResourceResponse};
-use crate::types::api::{APIRequest, AuthRequest, DatastoreRequest};
+use crate::types::api::{APIRequest, AuthRequest, DatastoreRequest, SettingsResponse};
 use crate::types::library::{LibraryBucket, LibraryItem};
-use crate::types::profile::{Auth, Profile};
+use crate::types::profile::{Auth, Profile, User};
+use crate::types::streams::StreamsItem;
+use crate::types::subtitles::SubtitlesItem;
+use chrono::{DateTime, Utc};
 use url::Url;
 
 pub type CtxStorageResponse = (
@@ -13,9 +16,22 @@ pub type CtxStorageResponse = (
     Option<LibraryBucket>,
 );
 
-pub type AuthResponse = (Auth, Vec<Descriptor>, Vec<LibraryItem>);
+pub type AuthResponse = (
+    Auth,
+    Vec<Descriptor>,
+    Vec<LibraryItem>,
+    Option<SettingsResponse>,
+);
 
-pub type LibraryPlanResponse = (Vec<String>, Vec<String>);
+#[derive(Clone, Default)]
+pub struct LibrarySyncPlan {
+    pub pull_ids: Vec<String>,
+    pub push_ids: Vec<String>,
+    pub pulled: usize,
+    pub pushed: usize,
+    // the library is in sync up to this mtime once every batch is done
+    pub synced_mtime: Option<DateTime<Utc>>,
+}
 
 //
 // Those messages are meant to be dispatched and hanled only inside stremio-core crate
@@ -23,18 +39,49 @@ pub type LibraryPlanResponse = (Vec<String>, Vec<String>);
 pub enum Internal {
     // Result for authenticate to API.
     CtxAuthResult(AuthRequest, Result<AuthResponse, CtxError>),
+    // Result for pull user from API.
+    UserAPIResult(APIRequest, Result<User, CtxError>),
+    // Result for push addons to API.
+    AddonsPushResult(APIRequest, Result<(), CtxError>),
     // Result for pull addons from API.
     AddonsAPIResult(APIRequest, Result<Vec<Descriptor>, CtxError>),
     // Result for library sync plan with API.
-    LibrarySyncPlanResult(DatastoreRequest, Result<LibraryPlanResponse, CtxError>),
-    // Result for pull library items from API.
-    LibraryPullResult(DatastoreRequest, Result<Vec<LibraryItem>, CtxError>),
+    LibrarySyncPlanResult(DatastoreRequest, Result<LibrarySyncPlan, CtxError>),
+    // Result for push library items to API.
+    LibraryPushResult(DatastoreRequest, Result<(), CtxError>),
+    // Result for push a batch of library items to API during sync.
+    LibrarySyncPushResult(DatastoreRequest, LibrarySyncPlan, Result<(), CtxError>),
+    // Result for pull a batch of library items from API during sync.
+    LibraryPullResult(
+        DatastoreRequest,
+        LibrarySyncPlan,
+        Result<Vec<LibraryItem>, CtxError>,
+    ),
     // Dispatched when library item needs to be updated in the memory, storage and API.
     UpdateLibraryItem(LibraryItem),
+    // Dispatched when library item notifications need to be set the same way as by the ctx action.
+    SetLibraryItemNotifications {
+        id: String,
+        no_notif: bool,
+    },
     // Dispatched when some of auth, addons or settings changed.
     ProfileChanged,
     // Dispatched when library changes with a flag if its already persisted.
     LibraryChanged(bool),
+    // Dispatched when requests are added to or removed from the outbox.
+    OutboxChanged,
+    // Dispatched when a search query needs to be recorded in the search history.
+    UpdateSearchHistory(String),
+    // Dispatched when search history changes.
+    SearchHistoryChanged,
+    // Dispatched when the last played stream of a video needs to be remembered.
+    UpdateStreamsItem(StreamsItem),
+    // Dispatched when streams bucket changes.
+    StreamsChanged,
+    // Dispatched when the subtitles choice of a video needs to be remembered.
+    UpdateSubtitlesItem(SubtitlesItem),
+    // Dispatched when subtitles bucket changes.
+    SubtitlesChanged,
     // Result for loading streaming server settings.
     StreamingServerSettingsResult(Url, Result<StreamingServerSettings, EnvError>),
     // Result for loading streaming server base url.
@@ -43,6 +90,10 @@ pub enum Internal {
     StreamingServerUpdateSettingsResult(Url, Result<(), EnvError>),
     // Result for fetching resource from addons.
     ResourceRequestResult(ResourceRequest, Box<Result<ResourceResponse, EnvError>>),
+    // Dispatched when the search query has not changed for the debounce delay.
+    SearchDebounced(String),
     // Result for fetching manifest from addon.
     ManifestRequestResult(Url, Result<Manifest, EnvError>),
+    // Dispatched periodically by the runtime scheduler.
+    Tick,
 }
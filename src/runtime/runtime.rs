@@ -1,10 +1,13 @@
-use crate::runtime::msg::{Action, Event, Msg};
+use crate::runtime::msg::{Action, Event, Internal, Msg};
 use crate::runtime::{Effect, Effects, Env, Model};
+use chrono::Duration;
 use derivative::Derivative;
 use enclose::enclose;
 use futures::channel::mpsc::{channel, Receiver, Sender};
-use futures::FutureExt;
+use futures::future::{abortable, AbortHandle};
+use futures::{future, FutureExt, StreamExt};
 use serde::Serialize;
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::sync::{Arc, LockResult, RwLock, RwLockReadGuard};
 
@@ -20,11 +23,42 @@ pub struct RuntimeAction<E: Env, M: Model<E>> {
     pub action: Action,
 }
 
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+struct EffectHandles<F> {
+    next_id: usize,
+    handles: HashMap<usize, (F, AbortHandle)>,
+}
+
+impl<F: PartialEq> EffectHandles<F> {
+    fn insert(&mut self, field: F, handle: AbortHandle) -> usize {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.handles.insert(id, (field, handle));
+        id
+    }
+    fn remove(&mut self, id: usize) {
+        self.handles.remove(&id);
+    }
+    fn abort(&mut self, field: &F) {
+        self.handles.retain(|_, (handle_field, handle)| {
+            if handle_field == field {
+                handle.abort();
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
 #[derive(Derivative)]
 #[derivative(Clone(bound = ""))]
 pub struct Runtime<E: Env, M: Model<E>> {
     model: Arc<RwLock<M>>,
     tx: Sender<RuntimeEvent>,
+    effect_handles: Arc<RwLock<EffectHandles<M::Field>>>,
+    scheduler_handle: Arc<RwLock<Option<AbortHandle>>>,
     env: PhantomData<E>,
 }
 
@@ -39,31 +73,73 @@ where
         let runtime = Runtime {
             model,
             tx,
+            effect_handles: Default::default(),
+            scheduler_handle: Default::default(),
             env: PhantomData,
         };
-        runtime.handle_effects(effects);
+        runtime.handle_effects(effects, None);
         (runtime, rx)
     }
     pub fn model(&self) -> LockResult<RwLockReadGuard<M>> {
         self.model.read()
     }
     pub fn dispatch(&self, action: RuntimeAction<E, M>) {
-        let effects = {
+        let (effects, field) = {
             let mut model = self.model.write().expect("model write failed");
             match action {
                 RuntimeAction {
                     field: Some(field),
                     action,
-                } => model.update_field(&Msg::Action(action), &field),
-                RuntimeAction { action, .. } => model.update(&Msg::Action(action)),
+                } => {
+                    let is_load = matches!(action, Action::Load(_));
+                    let is_unload = matches!(action, Action::Unload);
+                    let effects = model.update_field(&Msg::Action(action), &field);
+                    // Identical loads are skipped by the models, so their in-flight effects
+                    // are aborted only when superseded by new ones or when the field unloads.
+                    if is_unload || is_load && effects.has_futures() {
+                        self.abort_effects(&field);
+                    };
+                    (effects, Some(field))
+                }
+                RuntimeAction { action, .. } => (model.update(&Msg::Action(action)), None),
             }
         };
-        self.handle_effects(effects);
+        self.handle_effects(effects, field);
+    }
+    pub fn start_scheduler(&self, interval: Duration) {
+        self.stop_scheduler();
+        let (future, handle) = abortable(E::interval(interval).for_each(
+            enclose!((self.clone() => runtime) move |_| {
+                runtime.handle_effect_output(Msg::Internal(Internal::Tick));
+                future::ready(())
+            }),
+        ));
+        *self
+            .scheduler_handle
+            .write()
+            .expect("scheduler handle write failed") = Some(handle);
+        E::exec(future.map(|_| ()));
+    }
+    pub fn stop_scheduler(&self) {
+        if let Some(handle) = self
+            .scheduler_handle
+            .write()
+            .expect("scheduler handle write failed")
+            .take()
+        {
+            handle.abort();
+        };
+    }
+    fn abort_effects(&self, field: &M::Field) {
+        self.effect_handles
+            .write()
+            .expect("effect handles write failed")
+            .abort(field);
     }
     fn emit(&self, event: RuntimeEvent) {
         self.tx.clone().try_send(event).expect("emit event failed");
     }
-    fn handle_effects(&self, effects: Effects) {
+    fn handle_effects(&self, effects: Effects, field: Option<M::Field>) {
         if effects.has_changed {
             self.emit(RuntimeEvent::NewState);
         };
@@ -74,11 +150,31 @@ where
                     Effect::Msg(msg) => {
                         runtime.handle_effect_output(msg);
                     }
-                    Effect::Future(future) => {
-                        E::exec(future.then(enclose!((runtime) move |msg| async move {
-                            runtime.handle_effect_output(msg);
-                        })))
-                    }
+                    Effect::Future(future) => match &field {
+                        Some(field) => {
+                            let (future, handle) = abortable(future);
+                            let id = runtime
+                                .effect_handles
+                                .write()
+                                .expect("effect handles write failed")
+                                .insert(field.to_owned(), handle);
+                            E::exec(future.then(enclose!((runtime) move |result| async move {
+                                if let Ok(msg) = result {
+                                    runtime
+                                        .effect_handles
+                                        .write()
+                                        .expect("effect handles write failed")
+                                        .remove(id);
+                                    runtime.handle_effect_output(msg);
+                                };
+                            })))
+                        }
+                        None => {
+                            E::exec(future.then(enclose!((runtime) move |msg| async move {
+                                runtime.handle_effect_output(msg);
+                            })))
+                        }
+                    },
                 }
             }));
     }
@@ -89,7 +185,7 @@ where
             }
             Msg::Internal(_) => {
                 let effects = self.model.write().expect("model write failed").update(&msg);
-                self.handle_effects(effects);
+                self.handle_effects(effects, None);
             }
             Msg::Action(_) => {
                 panic!("effects are not allowed to resolve with action");
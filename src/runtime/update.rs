@@ -3,7 +3,7 @@ use crate::runtime::msg::Msg;
 use crate::runtime::{Effects, Env};
 
 pub trait Model<E: Env>: Update<E> {
-    type Field;
+    type Field: Clone + PartialEq;
     fn update_field(&mut self, msg: &Msg, field: &Self::Field) -> Effects;
 }
 
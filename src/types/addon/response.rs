@@ -1,30 +1,140 @@
 use crate::types::addon::DescriptorPreview;
+use crate::types::number_as_u32;
 use crate::types::resource::{MetaItem, MetaItemPreview, Stream, Subtitles};
-use derive_more::TryInto;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
-#[derive(Clone, TryInto, Serialize, Deserialize)]
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceCacheHints {
+    #[serde(
+        default,
+        deserialize_with = "number_as_u32",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub cache_max_age: Option<u32>,
+    #[serde(
+        default,
+        deserialize_with = "number_as_u32",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub stale_revalidate: Option<u32>,
+    #[serde(
+        default,
+        deserialize_with = "number_as_u32",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub stale_error: Option<u32>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[cfg_attr(test, derive(PartialEq))]
 #[serde(untagged)]
 pub enum ResourceResponse {
     Metas {
         metas: Vec<MetaItemPreview>,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
     #[serde(rename_all = "camelCase")]
     MetasDetailed {
         metas_detailed: Vec<MetaItem>,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
     Meta {
         meta: MetaItem,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
     Streams {
         streams: Vec<Stream>,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
     Subtitles {
         subtitles: Vec<Subtitles>,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
     Addons {
         addons: Vec<DescriptorPreview>,
+        #[serde(flatten)]
+        cache_hints: ResourceCacheHints,
     },
 }
+
+impl ResourceResponse {
+    pub fn cache_hints(&self) -> &ResourceCacheHints {
+        match self {
+            ResourceResponse::Metas { cache_hints, .. }
+            | ResourceResponse::MetasDetailed { cache_hints, .. }
+            | ResourceResponse::Meta { cache_hints, .. }
+            | ResourceResponse::Streams { cache_hints, .. }
+            | ResourceResponse::Subtitles { cache_hints, .. }
+            | ResourceResponse::Addons { cache_hints, .. } => cache_hints,
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for Vec<MetaItemPreview> {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::Metas { metas, .. } => Ok(metas),
+            _ => Err("Only Metas can be converted to Vec<MetaItemPreview>"),
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for Vec<MetaItem> {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::MetasDetailed { metas_detailed, .. } => Ok(metas_detailed),
+            _ => Err("Only MetasDetailed can be converted to Vec<MetaItem>"),
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for MetaItem {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::Meta { meta, .. } => Ok(meta),
+            _ => Err("Only Meta can be converted to MetaItem"),
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for Vec<Stream> {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::Streams { streams, .. } => Ok(streams),
+            _ => Err("Only Streams can be converted to Vec<Stream>"),
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for Vec<Subtitles> {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::Subtitles { subtitles, .. } => Ok(subtitles),
+            _ => Err("Only Subtitles can be converted to Vec<Subtitles>"),
+        }
+    }
+}
+
+impl TryFrom<ResourceResponse> for Vec<DescriptorPreview> {
+    type Error = &'static str;
+    fn try_from(response: ResourceResponse) -> Result<Self, Self::Error> {
+        match response {
+            ResourceResponse::Addons { addons, .. } => Ok(addons),
+            _ => Err("Only Addons can be converted to Vec<DescriptorPreview>"),
+        }
+    }
+}
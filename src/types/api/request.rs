@@ -1,6 +1,6 @@
 use crate::types::addon::Descriptor;
 use crate::types::library::LibraryItem;
-use crate::types::profile::{AuthKey, GDPRConsent};
+use crate::types::profile::{AuthKey, GDPRConsent, Settings, User};
 #[cfg(test)]
 use chrono::offset::TimeZone;
 use chrono::{DateTime, Utc};
@@ -22,6 +22,16 @@ pub enum APIRequest {
         auth_key: AuthKey,
     },
     #[serde(rename_all = "camelCase")]
+    GetUser {
+        auth_key: AuthKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    SaveUser {
+        auth_key: AuthKey,
+        #[serde(flatten)]
+        user: User,
+    },
+    #[serde(rename_all = "camelCase")]
     AddonCollectionGet {
         auth_key: AuthKey,
         update: bool,
@@ -32,6 +42,16 @@ pub enum APIRequest {
         addons: Vec<Descriptor>,
     },
     #[serde(rename_all = "camelCase")]
+    SettingsGet {
+        auth_key: AuthKey,
+    },
+    #[serde(rename_all = "camelCase")]
+    SettingsSet {
+        auth_key: AuthKey,
+        settings: Settings,
+        last_modified: DateTime<Utc>,
+    },
+    #[serde(rename_all = "camelCase")]
     Events {
         auth_key: AuthKey,
         events: Vec<serde_json::Value>,
@@ -44,8 +64,12 @@ impl APIMethodName for APIRequest {
             APIRequest::Auth(AuthRequest::Login { .. }) => "login",
             APIRequest::Auth(AuthRequest::Register { .. }) => "register",
             APIRequest::Logout { .. } => "logout",
+            APIRequest::GetUser { .. } => "getUser",
+            APIRequest::SaveUser { .. } => "saveUser",
             APIRequest::AddonCollectionGet { .. } => "addonCollectionGet",
             APIRequest::AddonCollectionSet { .. } => "addonCollectionSet",
+            APIRequest::SettingsGet { .. } => "settingsGet",
+            APIRequest::SettingsSet { .. } => "settingsSet",
             APIRequest::Events { .. } => "events",
         }
     }
@@ -1,5 +1,5 @@
 use crate::types::addon::Descriptor;
-use crate::types::profile::{AuthKey, User};
+use crate::types::profile::{AuthKey, Settings, User};
 use crate::types::True;
 use chrono::serde::ts_milliseconds;
 use chrono::{DateTime, Utc};
@@ -28,6 +28,13 @@ pub struct CollectionResponse {
     pub last_modified: DateTime<Utc>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsResponse {
+    pub settings: Settings,
+    pub last_modified: DateTime<Utc>,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct AuthResponse {
     #[serde(rename = "authKey")]
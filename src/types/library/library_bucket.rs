@@ -1,6 +1,7 @@
 use crate::constants::LIBRARY_RECENT_COUNT;
 use crate::types::library::LibraryItem;
 use crate::types::profile::UID;
+use chrono::{DateTime, Utc};
 use lazysort::SortedBy;
 use serde::{Deserialize, Serialize};
 use std::cmp;
@@ -11,6 +12,8 @@ use std::collections::{HashMap, HashSet};
 pub struct LibraryBucket {
     pub uid: UID,
     pub items: HashMap<String, LibraryItem>,
+    #[serde(default)]
+    pub last_synced: Option<DateTime<Utc>>,
 }
 
 impl LibraryBucket {
@@ -21,10 +24,12 @@ impl LibraryBucket {
                 .into_iter()
                 .map(|item| (item.id.to_owned(), item))
                 .collect(),
+            last_synced: None,
         }
     }
     pub fn merge_bucket(&mut self, bucket: LibraryBucket) {
         if self.uid == bucket.uid {
+            self.last_synced = self.last_synced.max(bucket.last_synced);
             self.merge_items(bucket.items.into_iter().map(|(_, item)| item).collect());
         };
     }
@@ -68,13 +73,19 @@ impl LibraryBucket {
 pub struct LibraryBucketRef<'a> {
     pub uid: &'a UID,
     pub items: HashMap<&'a str, &'a LibraryItem>,
+    pub last_synced: &'a Option<DateTime<Utc>>,
 }
 
 impl<'a> LibraryBucketRef<'a> {
-    pub fn new(uid: &'a UID, items: &[&'a LibraryItem]) -> Self {
+    pub fn new(
+        uid: &'a UID,
+        items: &[&'a LibraryItem],
+        last_synced: &'a Option<DateTime<Utc>>,
+    ) -> Self {
         LibraryBucketRef {
             uid,
             items: items.iter().map(|item| (item.id.as_str(), *item)).collect(),
+            last_synced,
         }
     }
 }
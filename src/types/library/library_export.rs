@@ -0,0 +1,19 @@
+use crate::constants::LIBRARY_EXPORT_VERSION;
+use crate::types::library::LibraryItem;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct LibraryExport {
+    pub version: u32,
+    pub items: Vec<LibraryItem>,
+}
+
+impl LibraryExport {
+    pub fn new(items: Vec<LibraryItem>) -> Self {
+        LibraryExport {
+            version: LIBRARY_EXPORT_VERSION,
+            items,
+        }
+    }
+}
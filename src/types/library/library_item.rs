@@ -1,5 +1,6 @@
 use crate::types::empty_string_as_none;
-use crate::types::resource::PosterShape;
+use crate::types::resource::{PosterShape, Video};
+use crate::types::watched_bitfield::{WatchedBitField, WatchedField};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -35,6 +36,14 @@ impl LibraryItem {
     pub fn is_in_continue_watching(&self) -> bool {
         self.should_sync() && (!self.removed || self.temp) && self.state.time_offset > 0
     }
+    #[inline]
+    pub fn progress(&self) -> f64 {
+        if self.state.duration > 0 {
+            self.state.time_offset as f64 / self.state.duration as f64
+        } else {
+            0.0
+        }
+    }
 }
 
 #[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
@@ -56,7 +65,7 @@ pub struct LibraryItemState {
         default
     )]
     pub video_id: Option<String>,
-    // @TODO bitfield, special type
+    // kept as the raw string so that malformed or legacy values are never lost
     #[serde(deserialize_with = "empty_string_as_none", default)]
     pub watched: Option<String>,
     // release date of last observed video
@@ -65,6 +74,76 @@ pub struct LibraryItemState {
     pub no_notif: bool,
 }
 
+impl LibraryItemState {
+    pub fn watched_field(&self) -> Option<WatchedField> {
+        self.watched
+            .as_deref()
+            .and_then(|watched| watched.parse().ok())
+    }
+    pub fn watched_bitfield(&self, videos: &[Video]) -> WatchedBitField {
+        WatchedBitField::new(
+            self.watched_field().as_ref(),
+            videos.iter().map(|video| video.id.to_owned()).collect(),
+        )
+    }
+    pub fn mark_as_watched(&mut self, watched: bool, now: DateTime<Utc>) {
+        if watched {
+            if self.flagged_watched == 0 {
+                self.flagged_watched = 1;
+                self.times_watched = self.times_watched.saturating_add(1);
+            };
+            self.last_watched = Some(now);
+        } else {
+            // the watch history is kept, only the current video is unmarked
+            self.flagged_watched = 0;
+            self.time_offset = 0;
+            self.time_watched = 0;
+        };
+    }
+    pub fn mark_videos_as_watched<'a>(
+        &mut self,
+        videos: &[Video],
+        video_ids: impl IntoIterator<Item = &'a str>,
+        watched: bool,
+        now: DateTime<Utc>,
+    ) {
+        // a watched value which can not be restored against these videos is kept as it is
+        let is_watched_restorable = match (&self.watched, self.watched_field()) {
+            (None, _) => true,
+            (Some(_), Some(watched_field)) => videos
+                .iter()
+                .any(|video| video.id == watched_field.anchor_video),
+            (Some(_), None) => false,
+        };
+        let mut watched_bitfield = self.watched_bitfield(videos);
+        for video_id in video_ids {
+            if !is_watched_restorable {
+                if self.video_id.as_deref() == Some(video_id) {
+                    self.flagged_watched = watched as u32;
+                };
+                continue;
+            };
+            if watched_bitfield.get_video(video_id) != watched {
+                watched_bitfield.set_video(video_id, watched);
+                self.times_watched = if watched {
+                    self.times_watched.saturating_add(1)
+                } else {
+                    self.times_watched.saturating_sub(1)
+                };
+            };
+            if self.video_id.as_deref() == Some(video_id) {
+                self.flagged_watched = watched as u32;
+            };
+        }
+        if watched {
+            self.last_watched = Some(now);
+        };
+        if is_watched_restorable {
+            self.watched = Some(WatchedField::from(&watched_bitfield).to_string());
+        };
+    }
+}
+
 #[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(Debug))]
 #[serde(rename_all = "camelCase")]
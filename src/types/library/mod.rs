@@ -1,5 +1,11 @@
 mod library_bucket;
 pub use library_bucket::*;
 
+mod library_export;
+pub use library_export::*;
+
 mod library_item;
 pub use library_item::*;
+
+mod trakt_history;
+pub use trakt_history::*;
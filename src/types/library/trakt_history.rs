@@ -0,0 +1,184 @@
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::Video;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+const IMDB_ID_PREFIX: &str = "tt";
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[serde(rename_all = "lowercase")]
+pub enum TraktHistoryType {
+    Movie,
+    Episode,
+}
+
+/// Flat history entry, so that it can be written both as JSON and as a CSV row
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct TraktHistoryEntry {
+    pub r#type: TraktHistoryType,
+    pub imdb_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub season: Option<u32>,
+    #[serde(default)]
+    pub episode: Option<u32>,
+    pub watched_at: DateTime<Utc>,
+}
+
+impl TraktHistoryEntry {
+    pub fn video_id(&self) -> String {
+        match (&self.r#type, self.season, self.episode) {
+            (TraktHistoryType::Episode, Some(season), Some(episode)) => {
+                format!("{}:{}:{}", self.imdb_id, season, episode)
+            }
+            _ => self.imdb_id.to_owned(),
+        }
+    }
+}
+
+pub fn to_trakt_history(library_item: &LibraryItem, videos: &[Video]) -> Vec<TraktHistoryEntry> {
+    if !library_item.id.starts_with(IMDB_ID_PREFIX) {
+        return vec![];
+    };
+    let watched_at = library_item
+        .state
+        .last_watched
+        .unwrap_or(library_item.mtime);
+    let entry = |season: Option<u32>, episode: Option<u32>| TraktHistoryEntry {
+        r#type: match (season, episode) {
+            (Some(_), Some(_)) => TraktHistoryType::Episode,
+            _ => TraktHistoryType::Movie,
+        },
+        imdb_id: library_item.id.to_owned(),
+        title: library_item.name.to_owned(),
+        season,
+        episode,
+        watched_at,
+    };
+    if library_item.r#type == "movie" {
+        return if library_item.state.times_watched > 0 || library_item.state.flagged_watched > 0 {
+            vec![entry(None, None)]
+        } else {
+            vec![]
+        };
+    };
+    let watched_bitfield = library_item.state.watched_bitfield(videos);
+    let mut entries = videos
+        .iter()
+        .filter(|video| watched_bitfield.get_video(&video.id))
+        .filter_map(|video| video.series_info.as_ref())
+        .map(|series_info| entry(Some(series_info.season), Some(series_info.episode)))
+        .collect::<Vec<_>>();
+    if library_item.state.flagged_watched > 0 {
+        let current_episode = library_item
+            .state
+            .video_id
+            .as_deref()
+            .and_then(|video_id| parse_episode_id(&library_item.id, video_id));
+        if let Some((season, episode)) = current_episode {
+            if !entries
+                .iter()
+                .any(|entry| entry.season == Some(season) && entry.episode == Some(episode))
+            {
+                entries.push(entry(Some(season), Some(episode)));
+            };
+        };
+    };
+    entries
+}
+
+pub fn from_trakt_history(
+    library: &LibraryBucket,
+    entries: &[TraktHistoryEntry],
+    videos: &HashMap<String, Vec<Video>>,
+    now: DateTime<Utc>,
+) -> Vec<LibraryItem> {
+    entries
+        .iter()
+        .filter(|entry| entry.imdb_id.starts_with(IMDB_ID_PREFIX))
+        .fold(
+            BTreeMap::<&str, Vec<&TraktHistoryEntry>>::new(),
+            |mut groups, entry| {
+                groups.entry(&entry.imdb_id).or_default().push(entry);
+                groups
+            },
+        )
+        .into_iter()
+        .filter_map(|(id, entries)| {
+            let last_entry = entries.iter().max_by_key(|entry| entry.watched_at)?;
+            let first_watched_at = entries.iter().map(|entry| entry.watched_at).min()?;
+            let current_item = library.items.get(id);
+            let mut library_item = current_item.cloned().unwrap_or_else(|| LibraryItem {
+                id: id.to_owned(),
+                name: last_entry.title.to_owned(),
+                r#type: match last_entry.r#type {
+                    TraktHistoryType::Movie => "movie".to_owned(),
+                    TraktHistoryType::Episode => "series".to_owned(),
+                },
+                poster: None,
+                poster_shape: Default::default(),
+                removed: false,
+                temp: false,
+                ctime: Some(first_watched_at),
+                mtime: now,
+                state: LibraryItemState::default(),
+                behavior_hints: Default::default(),
+            });
+            match last_entry.r#type {
+                TraktHistoryType::Movie => {
+                    library_item.state.flagged_watched = 1;
+                    library_item.state.times_watched =
+                        library_item.state.times_watched.max(entries.len() as u32);
+                }
+                TraktHistoryType::Episode => {
+                    let videos = videos.get(id).map(Vec::as_slice).unwrap_or_default();
+                    let video_ids = entries
+                        .iter()
+                        .map(|entry| entry.video_id())
+                        .filter(|video_id| videos.iter().any(|video| video.id == *video_id))
+                        .collect::<Vec<_>>();
+                    if !video_ids.is_empty() {
+                        library_item.state.mark_videos_as_watched(
+                            videos,
+                            video_ids.iter().map(String::as_str),
+                            true,
+                            last_entry.watched_at,
+                        );
+                    };
+                    if library_item.state.video_id.is_none() {
+                        library_item.state.video_id = Some(last_entry.video_id());
+                    };
+                }
+            };
+            library_item.state.last_watched = library_item
+                .state
+                .last_watched
+                .max(Some(last_entry.watched_at));
+            match current_item {
+                Some(current_item)
+                    if LibraryItem {
+                        mtime: current_item.mtime,
+                        ..library_item.to_owned()
+                    } == *current_item =>
+                {
+                    None
+                }
+                _ => Some(LibraryItem {
+                    mtime: now,
+                    ..library_item
+                }),
+            }
+        })
+        .collect()
+}
+
+fn parse_episode_id(id: &str, video_id: &str) -> Option<(u32, u32)> {
+    let mut parts = video_id.strip_prefix(id)?.strip_prefix(':')?.split(':');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(season), Some(episode), None) => Some((season.parse().ok()?, episode.parse().ok()?)),
+        _ => None,
+    }
+}
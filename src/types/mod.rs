@@ -1,11 +1,19 @@
 pub mod addon;
 pub mod api;
 pub mod library;
+pub mod outbox;
 pub mod profile;
 pub mod resource;
+pub mod search_history;
+pub mod streams;
+pub mod subtitles;
+pub mod watched_bitfield;
 
 mod empty_string_as_none;
 pub use empty_string_as_none::*;
 
+mod number_as_u32;
+pub use number_as_u32::*;
+
 mod r#true;
 pub use r#true::*;
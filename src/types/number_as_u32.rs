@@ -0,0 +1,15 @@
+use serde::de::Deserializer;
+use serde::Deserialize;
+use serde_json::Value;
+
+pub fn number_as_u32<'de, D>(deserializer: D) -> Result<Option<u32>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<Value>::deserialize(deserializer)? {
+        Some(Value::Number(number)) => Ok(number
+            .as_f64()
+            .map(|number| number.max(0.0).min(u32::MAX as f64) as u32)),
+        _ => Ok(None),
+    }
+}
@@ -0,0 +1,2 @@
+mod outbox_bucket;
+pub use outbox_bucket::*;
@@ -0,0 +1,77 @@
+use crate::constants::{OUTBOX_RETRY_BASE_DELAY, OUTBOX_RETRY_MAX_DELAY};
+use crate::types::addon::Descriptor;
+use crate::types::library::LibraryItem;
+use crate::types::profile::UID;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp;
+use std::collections::HashMap;
+
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[serde(rename_all = "camelCase")]
+pub struct OutboxBucket {
+    pub uid: UID,
+    pub library_items: HashMap<String, LibraryItem>,
+    pub addons: Option<Vec<Descriptor>>,
+    pub attempts: u32,
+    pub retry_after: Option<DateTime<Utc>>,
+}
+
+impl OutboxBucket {
+    pub fn new(uid: UID) -> Self {
+        OutboxBucket {
+            uid,
+            ..Default::default()
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.library_items.is_empty() && self.addons.is_none()
+    }
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        !self.is_empty()
+            && self
+                .retry_after
+                .iter()
+                .all(|retry_after| *retry_after <= now)
+    }
+    pub fn merge_library_items(&mut self, items: Vec<LibraryItem>) {
+        for new_item in items.into_iter() {
+            match self.library_items.get_mut(&new_item.id) {
+                Some(item) => {
+                    if new_item.mtime > item.mtime {
+                        *item = new_item;
+                    }
+                }
+                None => {
+                    self.library_items.insert(new_item.id.to_owned(), new_item);
+                }
+            }
+        }
+    }
+    pub fn remove_library_items(&mut self, items: &[LibraryItem]) {
+        for pushed_item in items.iter() {
+            if matches!(
+                self.library_items.get(&pushed_item.id),
+                Some(item) if item.mtime <= pushed_item.mtime
+            ) {
+                self.library_items.remove(&pushed_item.id);
+            };
+        }
+    }
+    pub fn schedule_retry(&mut self, now: DateTime<Utc>) {
+        let delay = OUTBOX_RETRY_BASE_DELAY
+            .checked_mul(2_i32.saturating_pow(cmp::min(self.attempts, 16)))
+            .map_or(*OUTBOX_RETRY_MAX_DELAY, |delay| {
+                cmp::min(delay, *OUTBOX_RETRY_MAX_DELAY)
+            });
+        self.attempts = self.attempts.saturating_add(1);
+        self.retry_after = Some(now + delay);
+    }
+    pub fn reset_retry(&mut self) {
+        if self.is_empty() {
+            self.attempts = 0;
+            self.retry_after = None;
+        };
+    }
+}
@@ -1,6 +1,7 @@
 use crate::constants::OFFICIAL_ADDONS;
 use crate::types::addon::Descriptor;
 use crate::types::profile::{Auth, AuthKey, Settings};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 pub type UID = Option<String>;
@@ -11,6 +12,7 @@ pub struct Profile {
     pub auth: Option<Auth>,
     pub addons: Vec<Descriptor>,
     pub settings: Settings,
+    pub settings_last_modified: Option<DateTime<Utc>>,
 }
 
 impl Default for Profile {
@@ -19,6 +21,7 @@ impl Default for Profile {
             auth: None,
             addons: OFFICIAL_ADDONS.to_owned(),
             settings: Settings::default(),
+            settings_last_modified: None,
         }
     }
 }
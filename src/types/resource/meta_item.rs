@@ -35,6 +35,7 @@ pub struct MetaItem {
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(debug_assertions, derive(Debug))]
+#[cfg_attr(test, derive(Default))]
 #[serde(rename_all = "camelCase")]
 pub struct MetaItemPreview {
     pub id: String,
@@ -43,6 +44,7 @@ pub struct MetaItemPreview {
     pub name: String,
     pub poster: Option<String>,
     pub logo: Option<String>,
+    pub popularity: Option<f64>,
     pub description: Option<String>,
     pub release_info: Option<String>,
     pub runtime: Option<String>,
@@ -0,0 +1,2 @@
+mod search_history_bucket;
+pub use search_history_bucket::*;
@@ -0,0 +1,47 @@
+use crate::constants::SEARCH_HISTORY_MAX_COUNT;
+use crate::types::profile::UID;
+use chrono::{DateTime, Utc};
+use lazysort::SortedBy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct SearchHistoryBucket {
+    pub uid: UID,
+    pub items: HashMap<String, DateTime<Utc>>,
+}
+
+impl SearchHistoryBucket {
+    pub fn new(uid: UID) -> Self {
+        SearchHistoryBucket {
+            uid,
+            items: HashMap::new(),
+        }
+    }
+    pub fn record(&mut self, query: &str, time: DateTime<Utc>) {
+        let query = query.trim();
+        if query.is_empty() {
+            return;
+        };
+        self.items.insert(query.to_owned(), time);
+        if self.items.len() > SEARCH_HISTORY_MAX_COUNT {
+            let expired_queries = self
+                .recent()
+                .skip(SEARCH_HISTORY_MAX_COUNT)
+                .cloned()
+                .collect::<Vec<_>>();
+            for query in expired_queries.iter() {
+                self.items.remove(query);
+            }
+        };
+    }
+    pub fn recent(&self) -> impl Iterator<Item = &String> {
+        self.items
+            .iter()
+            .sorted_by(|(a_query, a_time), (b_query, b_time)| {
+                b_time.cmp(a_time).then_with(|| a_query.cmp(b_query))
+            })
+            .map(|(query, _)| query)
+    }
+}
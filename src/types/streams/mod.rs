@@ -0,0 +1,5 @@
+mod streams_bucket;
+pub use streams_bucket::*;
+
+mod streams_item;
+pub use streams_item::*;
@@ -0,0 +1,57 @@
+use crate::constants::STREAMS_MAX_COUNT;
+use crate::types::profile::UID;
+use crate::types::streams::StreamsItem;
+use lazysort::SortedBy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct StreamsBucket {
+    pub uid: UID,
+    pub items: HashMap<String, HashMap<String, StreamsItem>>,
+}
+
+impl StreamsBucket {
+    pub fn new(uid: UID) -> Self {
+        StreamsBucket {
+            uid,
+            items: HashMap::new(),
+        }
+    }
+    pub fn get(&self, meta_id: &str, video_id: &str) -> Option<&StreamsItem> {
+        self.items
+            .get(meta_id)
+            .and_then(|meta_items| meta_items.get(video_id))
+    }
+    pub fn last_for_meta(&self, meta_id: &str) -> Option<&StreamsItem> {
+        self.items
+            .get(meta_id)
+            .and_then(|meta_items| meta_items.values().max_by_key(|item| item.mtime))
+    }
+    pub fn insert(&mut self, item: StreamsItem) {
+        self.items
+            .entry(item.meta_id.to_owned())
+            .or_default()
+            .insert(item.video_id.to_owned(), item);
+        let count = self.items.values().map(HashMap::len).sum::<usize>();
+        if count > STREAMS_MAX_COUNT {
+            let expired_items = self
+                .items
+                .values()
+                .flat_map(HashMap::values)
+                .sorted_by(|a, b| b.mtime.cmp(&a.mtime))
+                .skip(STREAMS_MAX_COUNT)
+                .map(|item| (item.meta_id.to_owned(), item.video_id.to_owned()))
+                .collect::<Vec<_>>();
+            for (meta_id, video_id) in expired_items.iter() {
+                if let Some(meta_items) = self.items.get_mut(meta_id) {
+                    meta_items.remove(video_id);
+                    if meta_items.is_empty() {
+                        self.items.remove(meta_id);
+                    };
+                };
+            }
+        };
+    }
+}
@@ -0,0 +1,15 @@
+use crate::types::resource::Stream;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[serde(rename_all = "camelCase")]
+pub struct StreamsItem {
+    pub meta_id: String,
+    pub video_id: String,
+    pub stream: Stream,
+    pub transport_url: Url,
+    pub mtime: DateTime<Utc>,
+}
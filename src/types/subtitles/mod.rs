@@ -0,0 +1,5 @@
+mod subtitles_bucket;
+pub use subtitles_bucket::*;
+
+mod subtitles_item;
+pub use subtitles_item::*;
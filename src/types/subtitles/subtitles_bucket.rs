@@ -0,0 +1,31 @@
+use crate::types::profile::UID;
+use crate::types::subtitles::SubtitlesItem;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct SubtitlesBucket {
+    pub uid: UID,
+    pub items: HashMap<String, HashMap<String, SubtitlesItem>>,
+}
+
+impl SubtitlesBucket {
+    pub fn new(uid: UID) -> Self {
+        SubtitlesBucket {
+            uid,
+            items: HashMap::new(),
+        }
+    }
+    pub fn get(&self, library_item_id: &str, video_id: &str) -> Option<&SubtitlesItem> {
+        self.items
+            .get(library_item_id)
+            .and_then(|library_item_items| library_item_items.get(video_id))
+    }
+    pub fn insert(&mut self, item: SubtitlesItem) {
+        self.items
+            .entry(item.library_item_id.to_owned())
+            .or_default()
+            .insert(item.video_id.to_owned(), item);
+    }
+}
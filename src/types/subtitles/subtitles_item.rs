@@ -0,0 +1,15 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitlesItem {
+    pub library_item_id: String,
+    pub video_id: String,
+    pub subtitles_id: Option<String>,
+    pub subtitles_lang: Option<String>,
+    pub delay: Option<i64>,
+    pub size: Option<u8>,
+    pub mtime: DateTime<Utc>,
+}
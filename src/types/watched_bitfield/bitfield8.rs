@@ -0,0 +1,68 @@
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+#[derive(Clone, PartialEq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct BitField8 {
+    pub length: usize,
+    values: Vec<u8>,
+}
+
+impl BitField8 {
+    pub fn new(length: usize) -> Self {
+        BitField8 {
+            length,
+            values: vec![0; length.div_ceil(8)],
+        }
+    }
+    pub fn get(&self, index: usize) -> bool {
+        self.values
+            .get(index / 8)
+            .map(|value| value & (1 << (index % 8)) != 0)
+            .unwrap_or_default()
+    }
+    pub fn set(&mut self, index: usize, value: bool) {
+        if index >= self.length {
+            self.resize(index + 1);
+        };
+        if value {
+            self.values[index / 8] |= 1 << (index % 8);
+        } else {
+            self.values[index / 8] &= !(1 << (index % 8));
+        };
+    }
+    pub fn resize(&mut self, length: usize) {
+        self.length = length;
+        self.values.resize(length.div_ceil(8), 0);
+    }
+    pub fn last_index_of(&self, value: bool) -> Option<usize> {
+        (0..self.length)
+            .rev()
+            .find(|index| self.get(*index) == value)
+    }
+    pub fn to_packed(&self) -> String {
+        let mut encoder = ZlibEncoder::new(vec![], Compression::default());
+        encoder
+            .write_all(&self.values)
+            .expect("zlib encoding failed");
+        base64::encode(encoder.finish().expect("zlib encoding failed"))
+    }
+}
+
+impl FromStr for BitField8 {
+    type Err = &'static str;
+    fn from_str(packed: &str) -> Result<Self, Self::Err> {
+        let compressed = base64::decode(packed).map_err(|_| "Invalid base64 bitfield")?;
+        let mut values = vec![];
+        ZlibDecoder::new(compressed.as_slice())
+            .read_to_end(&mut values)
+            .map_err(|_| "Invalid zlib bitfield")?;
+        Ok(BitField8 {
+            length: values.len() * 8,
+            values,
+        })
+    }
+}
@@ -0,0 +1,8 @@
+mod bitfield8;
+pub use bitfield8::*;
+
+mod watched_field;
+pub use watched_field::*;
+
+mod watched_bitfield;
+pub use watched_bitfield::*;
@@ -0,0 +1,71 @@
+use crate::types::watched_bitfield::{BitField8, WatchedField};
+
+/// Watched state of every video of a meta item, in the order of `MetaItem.videos`.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct WatchedBitField {
+    bitfield: BitField8,
+    video_ids: Vec<String>,
+}
+
+impl WatchedBitField {
+    /// Restores the watched state against the current videos list.
+    /// The previous videos list is assumed to be the same as the current one up to the anchor,
+    /// so the bitfield is shifted when videos were added or removed before it.
+    pub fn new(watched: Option<&WatchedField>, video_ids: Vec<String>) -> Self {
+        let mut bitfield = BitField8::new(video_ids.len());
+        if let Some(watched) = watched {
+            if let Some(anchor_index) = video_ids
+                .iter()
+                .position(|video_id| *video_id == watched.anchor_video)
+            {
+                let offset = watched.anchor_length as isize - anchor_index as isize - 1;
+                for index in 0..video_ids.len() {
+                    let prev_index = index as isize + offset;
+                    if prev_index >= 0 && (prev_index as usize) < watched.bitfield.length {
+                        bitfield.set(index, watched.bitfield.get(prev_index as usize));
+                    };
+                }
+            };
+        };
+        WatchedBitField {
+            bitfield,
+            video_ids,
+        }
+    }
+    pub fn get(&self, index: usize) -> bool {
+        self.bitfield.get(index)
+    }
+    pub fn set(&mut self, index: usize, watched: bool) {
+        if index < self.video_ids.len() {
+            self.bitfield.set(index, watched);
+        };
+    }
+    pub fn get_video(&self, video_id: &str) -> bool {
+        self.video_ids
+            .iter()
+            .position(|id| id == video_id)
+            .map(|index| self.get(index))
+            .unwrap_or_default()
+    }
+    pub fn set_video(&mut self, video_id: &str, watched: bool) {
+        if let Some(index) = self.video_ids.iter().position(|id| id == video_id) {
+            self.set(index, watched);
+        };
+    }
+}
+
+impl From<&WatchedBitField> for WatchedField {
+    fn from(watched: &WatchedBitField) -> Self {
+        let anchor_index = watched.bitfield.last_index_of(true).unwrap_or_default();
+        WatchedField {
+            anchor_video: watched
+                .video_ids
+                .get(anchor_index)
+                .cloned()
+                .unwrap_or_default(),
+            anchor_length: anchor_index + 1,
+            bitfield: watched.bitfield.to_owned(),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::types::watched_bitfield::BitField8;
+use serde::de::{self, Deserialize, Deserializer};
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The serialized watched state of a library item in the format `{anchor_video}:{anchor_length}:{bitfield}`.
+/// The bitfield is zlib compressed and base64 encoded.
+/// The anchor is the last watched video and the anchor length is its position in the videos list + 1.
+#[derive(Clone, PartialEq)]
+#[cfg_attr(debug_assertions, derive(Debug))]
+pub struct WatchedField {
+    pub anchor_video: String,
+    pub anchor_length: usize,
+    pub bitfield: BitField8,
+}
+
+impl FromStr for WatchedField {
+    type Err = &'static str;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        // the anchor video id may contain colons
+        let mut components = value.rsplitn(3, ':');
+        let bitfield = components
+            .next()
+            .ok_or("Missing bitfield")?
+            .parse::<BitField8>()?;
+        let anchor_length = components
+            .next()
+            .ok_or("Missing anchor length")?
+            .parse::<usize>()
+            .map_err(|_| "Invalid anchor length")?;
+        let anchor_video = components.next().ok_or("Missing anchor video")?.to_owned();
+        Ok(WatchedField {
+            anchor_video,
+            anchor_length,
+            bitfield,
+        })
+    }
+}
+
+impl fmt::Display for WatchedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}",
+            self.anchor_video,
+            self.anchor_length,
+            self.bitfield.to_packed()
+        )
+    }
+}
+
+impl Serialize for WatchedField {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for WatchedField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
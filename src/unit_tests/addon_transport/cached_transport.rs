@@ -0,0 +1,150 @@
+use crate::addon_transport::{AddonCachedTransport, AddonHTTPTransport, AddonTransport};
+use crate::constants::ADDON_RESOURCE_CACHE_SIZE;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{ResourceCacheHints, ResourcePath, ResourceResponse};
+use crate::types::profile::Settings;
+use crate::unit_tests::{Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use chrono::Duration;
+use futures::executor::block_on;
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    let cache_max_age = if request.url.contains("max-age") {
+        Some(60)
+    } else {
+        None
+    };
+    future::ok(Box::new(ResourceResponse::Streams {
+        streams: vec![],
+        cache_hints: ResourceCacheHints {
+            cache_max_age,
+            ..Default::default()
+        },
+    }) as Box<dyn Any>)
+    .boxed_local()
+}
+
+fn transport(transport_url: &str) -> AddonCachedTransport<TestEnv> {
+    let transport_url = Url::parse(transport_url).unwrap();
+    AddonCachedTransport::new(
+        transport_url.to_owned(),
+        Box::new(AddonHTTPTransport::<TestEnv>::new(transport_url)),
+    )
+}
+
+fn path(id: &str) -> ResourcePath {
+    ResourcePath::without_extra("stream", "movie", id)
+}
+
+fn requests_count() -> usize {
+    REQUESTS.read().unwrap().len()
+}
+
+#[test]
+fn cached_transport_dedupe() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let transport = transport("https://dedupe.addon/manifest.json");
+    let (first, second) = block_on(future::join(
+        transport.resource(&path("tt1")),
+        transport.resource(&path("tt1")),
+    ));
+    assert!(first.is_ok() && second.is_ok(), "Both requests resolved");
+    assert_eq!(requests_count(), 1, "Identical requests collapsed");
+}
+
+#[test]
+fn cached_transport_ttl() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let transport = transport("https://ttl.addon/manifest.json");
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    TestEnv::advance_clock(Duration::minutes(5));
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    assert_eq!(requests_count(), 1, "Response served from cache");
+    TestEnv::advance_clock(Duration::minutes(6));
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    assert_eq!(requests_count(), 2, "Expired response fetched again");
+}
+
+#[test]
+fn cached_transport_max_age() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let transport = transport("https://max-age.addon/manifest.json");
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    TestEnv::advance_clock(Duration::seconds(30));
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    assert_eq!(requests_count(), 1, "Response served from cache");
+    TestEnv::advance_clock(Duration::seconds(31));
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    assert_eq!(
+        requests_count(),
+        2,
+        "Response fetched again after its max age"
+    );
+}
+
+#[test]
+fn cached_transport_lru_eviction() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let transport = transport("https://lru.addon/manifest.json");
+    for id in 0..ADDON_RESOURCE_CACHE_SIZE {
+        block_on(transport.resource(&path(&id.to_string()))).unwrap();
+    }
+    block_on(transport.resource(&path("0"))).unwrap();
+    block_on(transport.resource(&path("new"))).unwrap();
+    assert_eq!(
+        requests_count(),
+        ADDON_RESOURCE_CACHE_SIZE + 1,
+        "Cache filled"
+    );
+    block_on(transport.resource(&path("0"))).unwrap();
+    assert_eq!(
+        requests_count(),
+        ADDON_RESOURCE_CACHE_SIZE + 1,
+        "Recently used response kept"
+    );
+    block_on(transport.resource(&path("1"))).unwrap();
+    assert_eq!(
+        requests_count(),
+        ADDON_RESOURCE_CACHE_SIZE + 2,
+        "Least recently used response evicted"
+    );
+}
+
+#[test]
+fn cached_transport_cleared_on_profile_changed() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let transport = transport("https://profile-changed.addon/manifest.json");
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSettings(Settings {
+                binge_watching: !Settings::default().binge_watching,
+                ..Settings::default()
+            })),
+        })
+    });
+    block_on(transport.resource(&path("tt1"))).unwrap();
+    assert_eq!(
+        requests_count(),
+        2,
+        "Response fetched again after the profile changed"
+    );
+}
@@ -0,0 +1 @@
+mod cached_transport;
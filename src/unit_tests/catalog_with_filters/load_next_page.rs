@@ -0,0 +1,188 @@
+use crate::constants::CATALOG_PAGE_SIZE;
+use crate::models::catalog_with_filters::{CatalogWithFilters, Selected};
+use crate::models::common::Loadable;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCatalogWithFilters, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, ExtraProp, Manifest, ManifestCatalog, ManifestExtra, ResourcePath, ResourceRequest,
+    ResourceResponse,
+};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItemPreview;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use std::ops::Range;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    discover: CatalogWithFilters<MetaItemPreview>,
+}
+
+fn metas(ids: Range<usize>) -> Box<dyn Any> {
+    Box::new(ResourceResponse::Metas {
+        metas: ids
+            .map(|id| MetaItemPreview {
+                id: format!("tt{}", id),
+                r#type: "movie".to_owned(),
+                ..Default::default()
+            })
+            .collect(),
+        cache_hints: Default::default(),
+    })
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request {
+        Request { url, .. } if url == "https://load-next-page.addon/catalog/movie/top.json" => {
+            future::ok(metas(0..CATALOG_PAGE_SIZE)).boxed_local()
+        }
+        Request { url, .. }
+            if url == "https://load-next-page.addon/catalog/movie/top/skip=100.json" =>
+        {
+            future::ok(metas(CATALOG_PAGE_SIZE - 5..CATALOG_PAGE_SIZE + 45)).boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+#[test]
+fn catalog_with_filters_load_next_page() {
+    let transport_url = Url::parse("https://load-next-page.addon/manifest.json").unwrap();
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: Manifest {
+                            id: "id".to_owned(),
+                            version: Version::new(0, 0, 1),
+                            name: "name".to_owned(),
+                            contact_email: None,
+                            description: None,
+                            logo: None,
+                            background: None,
+                            types: vec!["movie".to_owned()],
+                            resources: vec![],
+                            id_prefixes: None,
+                            catalogs: vec![ManifestCatalog {
+                                r#type: "movie".to_owned(),
+                                id: "top".to_owned(),
+                                name: None,
+                                extra: ManifestExtra::Full {
+                                    props: vec![ExtraProp {
+                                        name: "skip".to_owned(),
+                                        is_required: false,
+                                        options: None,
+                                        options_limit: Default::default(),
+                                    }],
+                                },
+                            }],
+                            addon_catalogs: vec![],
+                            behavior_hints: Default::default(),
+                        },
+                        transport_url: transport_url.to_owned(),
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            discover: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::CatalogWithFilters(Selected {
+                request: ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("catalog", "movie", "top"),
+                ),
+            })),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().discover.has_next_page,
+        "Full first page has next page"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::CatalogWithFilters(ActionCatalogWithFilters::LoadNextPage),
+        })
+    });
+    let pages = runtime
+        .model()
+        .unwrap()
+        .discover
+        .catalog
+        .iter()
+        .map(|page| match &page.content {
+            Loadable::Ready(content) => content.len(),
+            _ => 0,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pages,
+        vec![CATALOG_PAGE_SIZE, 45],
+        "Next page appended without duplicated items"
+    );
+    assert!(
+        !runtime.model().unwrap().discover.has_next_page,
+        "Short page has no next page"
+    );
+    assert!(
+        runtime
+            .model()
+            .unwrap()
+            .discover
+            .selectable
+            .next_page
+            .is_none(),
+        "No next page selectable"
+    );
+    let catalog = serde_json::to_value(&runtime.model().unwrap().discover).unwrap();
+    assert_eq!(
+        catalog["catalog"]["content"]["content"]
+            .as_array()
+            .map(|content| content.len()),
+        Some(CATALOG_PAGE_SIZE + 45),
+        "Pages serialized as a single catalog"
+    );
+    assert_eq!(catalog["hasNextPage"], false, "Has next page serialized");
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::CatalogWithFilters(ActionCatalogWithFilters::LoadNextPage),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().discover.catalog.len(),
+        2,
+        "No page appended at the end of the catalog"
+    );
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://load-next-page.addon/catalog/movie/top.json",
+            "https://load-next-page.addon/catalog/movie/top/skip=100.json"
+        ],
+        "No request sent at the end of the catalog"
+    );
+}
@@ -31,7 +31,7 @@ fn actionctx_addtolibrary() {
                 url, method, body, ..
             } if url == "https://api.strem.io/api/datastorePut"
                 && method == "POST"
-                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\",\"changes\":[{\"_id\":\"id\",\"name\":\"name\",\"type\":\"type\",\"poster\":null,\"posterShape\":\"poster\",\"removed\":false,\"temp\":false,\"_ctime\":\"2020-01-01T00:00:00Z\",\"_mtime\":\"2020-01-01T00:00:00Z\",\"state\":{\"lastWatched\":null,\"timeWatched\":0,\"timeOffset\":0,\"overallTimeWatched\":0,\"timesWatched\":0,\"flaggedWatched\":0,\"duration\":0,\"video_id\":null,\"watched\":null,\"lastVidReleased\":null,\"noNotif\":false},\"behaviorHints\":{\"defaultVideoId\":null}}]}" =>
+                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\",\"changes\":[{\"_id\":\"id\",\"name\":\"name\",\"type\":\"type\",\"poster\":null,\"posterShape\":\"poster\",\"removed\":false,\"temp\":false,\"_ctime\":\"2020-01-01T00:00:00Z\",\"_mtime\":\"2020-01-01T00:00:00Z\",\"state\":{\"lastWatched\":null,\"timeWatched\":0,\"timeOffset\":0,\"overallTimeWatched\":0,\"timesWatched\":0,\"flaggedWatched\":0,\"duration\":0,\"video_id\":null,\"watched\":null,\"lastVidReleased\":\"2020-01-01T00:00:00Z\",\"noNotif\":false},\"behaviorHints\":{\"defaultVideoId\":null}}]}" =>
             {
                 future::ok(Box::new(APIResult::Ok {
                     result: SuccessResponse { success: True {} },
@@ -46,10 +46,11 @@ fn actionctx_addtolibrary() {
         name: "name".to_owned(),
         poster: None,
         logo: None,
+        popularity: None,
         description: None,
         release_info: None,
         runtime: None,
-        released: None,
+        released: Some(Utc.ymd(2019, 12, 1).and_hms_milli(0, 0, 0, 0)),
         poster_shape: Default::default(),
         trailer_streams: vec![],
         behavior_hints: Default::default(),
@@ -60,7 +61,10 @@ fn actionctx_addtolibrary() {
         temp: false,
         ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
         mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
-        state: Default::default(),
+        state: LibraryItemState {
+            last_vid_released: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            ..LibraryItemState::default()
+        },
         name: "name".to_owned(),
         r#type: "type".to_owned(),
         poster: None,
@@ -165,10 +169,11 @@ fn actionctx_addtolibrary_already_added() {
         poster: Some("poster".to_owned()),
         poster_shape: PosterShape::Square,
         logo: None,
+        popularity: None,
         description: None,
         release_info: None,
         runtime: None,
-        released: None,
+        released: Some(Utc.ymd(2019, 12, 2).and_hms_milli(0, 0, 0, 0)),
         trailer_streams: vec![],
         behavior_hints: MetaItemBehaviorHints {
             default_video_id: Some("video_id2".to_owned()),
@@ -188,6 +193,7 @@ fn actionctx_addtolibrary_already_added() {
         mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
         state: LibraryItemState {
             video_id: Some("video_id".to_owned()),
+            last_vid_released: Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
             ..LibraryItemState::default()
         },
         behavior_hints: LibraryItemBehaviorHints {
@@ -222,6 +228,7 @@ fn actionctx_addtolibrary_already_added() {
                     )]
                     .into_iter()
                     .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
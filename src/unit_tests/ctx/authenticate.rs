@@ -3,10 +3,12 @@ use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Effects, Env, Runtime, RuntimeAction, TryEnvFuture};
 use crate::types::api::{
-    APIResult, AuthRequest, AuthResponse, CollectionResponse, GDPRConsentRequest,
+    APIError, APIResult, AuthRequest, AuthResponse, CollectionResponse, GDPRConsentRequest,
+    LibraryItemModified, SettingsResponse, SuccessResponse,
 };
 use crate::types::library::{LibraryBucket, LibraryItem};
-use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, Settings, User};
+use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
 };
@@ -72,6 +74,26 @@ fn actionctx_authenticate_login() {
                     result: Vec::<LibraryItem>::new(),
                 }) as Box<dyn Any>).boxed_local()
             }
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/settingsGet"
+                && method == "POST"
+                && body == "{\"type\":\"SettingsGet\",\"authKey\":\"auth_key\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: None::<SettingsResponse>,
+                }) as Box<dyn Any>).boxed_local()
+            }
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/datastoreMeta"
+                && method == "POST"
+                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>).boxed_local()
+            }
             _ => default_fetch_handler(request),
         }
     }
@@ -168,8 +190,8 @@ fn actionctx_authenticate_login() {
     );
     assert_eq!(
         REQUESTS.read().unwrap().len(),
-        3,
-        "Three requests have been sent"
+        5,
+        "Five requests have been sent"
     );
     assert_eq!(
         REQUESTS.read().unwrap().get(0).unwrap().to_owned(),
@@ -204,6 +226,26 @@ fn actionctx_authenticate_login() {
         },
         "DatastoreGet request has been sent"
     );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(3).unwrap().to_owned(),
+        Request {
+            url: "https://api.strem.io/api/settingsGet".to_owned(),
+            method: "POST".to_owned(),
+            body: "{\"type\":\"SettingsGet\",\"authKey\":\"auth_key\"}".to_owned(),
+            ..Default::default()
+        },
+        "SettingsGet request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(4).unwrap().to_owned(),
+        Request {
+            url: "https://api.strem.io/api/datastoreMeta".to_owned(),
+            method: "POST".to_owned(),
+            body: "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}".to_owned(),
+            ..Default::default()
+        },
+        "DatastoreMeta request has been sent"
+    );
 }
 
 #[test]
@@ -263,6 +305,26 @@ fn actionctx_authenticate_register() {
                     result: Vec::<LibraryItem>::new(),
                 }) as Box<dyn Any>).boxed_local()
             }
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/settingsGet"
+                && method == "POST"
+                && body == "{\"type\":\"SettingsGet\",\"authKey\":\"auth_key\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: None::<SettingsResponse>,
+                }) as Box<dyn Any>).boxed_local()
+            }
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/datastoreMeta"
+                && method == "POST"
+                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>).boxed_local()
+            }
             _ => default_fetch_handler(request),
         }
     }
@@ -367,8 +429,8 @@ fn actionctx_authenticate_register() {
     );
     assert_eq!(
         REQUESTS.read().unwrap().len(),
-        3,
-        "Three requests have been sent"
+        5,
+        "Five requests have been sent"
     );
     assert_eq!(
         REQUESTS.read().unwrap().get(0).unwrap().to_owned(),
@@ -403,4 +465,371 @@ fn actionctx_authenticate_register() {
         },
         "DatastoreGet request has been sent"
     );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(3).unwrap().to_owned(),
+        Request {
+            url: "https://api.strem.io/api/settingsGet".to_owned(),
+            method: "POST".to_owned(),
+            body: "{\"type\":\"SettingsGet\",\"authKey\":\"auth_key\"}".to_owned(),
+            ..Default::default()
+        },
+        "SettingsGet request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(4).unwrap().to_owned(),
+        Request {
+            url: "https://api.strem.io/api/datastoreMeta".to_owned(),
+            method: "POST".to_owned(),
+            body: "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}".to_owned(),
+            ..Default::default()
+        },
+        "DatastoreMeta request has been sent"
+    );
+}
+
+#[test]
+fn actionctx_authenticate_login_with_settings() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, .. } if url == "https://api.strem.io/api/login" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: AuthResponse {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User::default(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/addonCollectionGet" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: CollectionResponse {
+                        addons: vec![],
+                        last_modified: TestEnv::now(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/datastoreGet" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItem>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/settingsGet" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Some(SettingsResponse {
+                        settings: Settings {
+                            subtitles_language: "bg".to_owned(),
+                            ..Settings::default()
+                        },
+                        last_modified: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+                    }),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/datastoreMeta"
+                && method == "POST"
+                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    settings: Settings {
+                        subtitles_language: "eng".to_owned(),
+                        subtitles_size: 150,
+                        ..Settings::default()
+                    },
+                    settings_last_modified: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Authenticate(AuthRequest::Login {
+                email: "user_email".into(),
+                password: "user_password".into(),
+                facebook: false,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.settings,
+        Settings {
+            subtitles_language: "bg".to_owned(),
+            ..Settings::default()
+        },
+        "newer settings pulled successfully in memory"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.settings_last_modified,
+        Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+        "settings last modified updated successfully in memory"
+    );
+    assert_eq!(
+        serde_json::from_str::<Profile>(&STORAGE.read().unwrap().get(PROFILE_STORAGE_KEY).unwrap())
+            .unwrap()
+            .settings,
+        Settings {
+            subtitles_language: "bg".to_owned(),
+            ..Settings::default()
+        },
+        "newer settings pulled successfully in storage"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        5,
+        "Five requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_authenticate_login_with_anonymous_library() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/login" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: AuthResponse {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: TestEnv::now(),
+                            date_registered: TestEnv::now(),
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: true,
+                            },
+                        },
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/addonCollectionGet" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: CollectionResponse {
+                        addons: vec![],
+                        last_modified: TestEnv::now(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastoreGet" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItem>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/settingsGet" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: None::<SettingsResponse>,
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastoreMeta" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: Default::default(),
+        name: "name".to_owned(),
+        r#type: "type".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Authenticate(AuthRequest::Login {
+                email: "user_email".into(),
+                password: "user_password".into(),
+                facebook: false,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.library,
+        LibraryBucket::new(Some("user_id".to_owned()), vec![library_item.to_owned()]),
+        "Anonymous library items merged in memory"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(5).unwrap().url,
+        "https://api.strem.io/api/datastorePut".to_owned(),
+        "datastorePut request has been sent"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&REQUESTS.read().unwrap().get(5).unwrap().body)
+            .unwrap()["changes"][0]["_id"],
+        "id",
+        "Anonymous library item pushed to API"
+    );
+}
+
+#[test]
+fn actionctx_authenticate_login_with_settings_error() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, .. } if url == "https://api.strem.io/api/login" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: AuthResponse {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User::default(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/addonCollectionGet" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: CollectionResponse {
+                        addons: vec![],
+                        last_modified: TestEnv::now(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/datastoreGet" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItem>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/settingsGet" => {
+                future::ok(Box::new(APIResult::<Option<SettingsResponse>>::Err {
+                    error: APIError {
+                        message: "Method not found".to_owned(),
+                        code: 404,
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, .. } if url == "https://api.strem.io/api/datastoreMeta" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    settings: Settings {
+                        subtitles_language: "eng".to_owned(),
+                        ..Settings::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::Authenticate(AuthRequest::Login {
+                email: "user_email".into(),
+                password: "user_password".into(),
+                facebook: false,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.auth_key(),
+        Some(&AuthKey("auth_key".to_owned())),
+        "user logged in despite settings error"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .profile
+            .settings
+            .subtitles_language,
+        "eng",
+        "local settings kept in memory"
+    );
 }
@@ -0,0 +1,59 @@
+use crate::constants::SEARCH_HISTORY_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Env, Runtime, RuntimeAction};
+use crate::types::search_history::SearchHistoryBucket;
+use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_clearsearchhistory() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let mut search_history = SearchHistoryBucket::new(Some("id".to_owned()));
+    search_history.record("query", TestEnv::now());
+    TestEnv::reset();
+    STORAGE.write().unwrap().insert(
+        SEARCH_HISTORY_STORAGE_KEY.to_owned(),
+        serde_json::to_string(&search_history).unwrap(),
+    );
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                search_history,
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::ClearSearchHistory),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.search_history,
+        SearchHistoryBucket::new(Some("id".to_owned())),
+        "search history updated successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(SEARCH_HISTORY_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<SearchHistoryBucket>(&data).unwrap()
+                    == SearchHistoryBucket::new(Some("id".to_owned()))
+            }),
+        "search history updated successfully in storage"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
@@ -0,0 +1,75 @@
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx, Event};
+use crate::runtime::{Effects, Runtime, RuntimeAction, RuntimeEvent};
+use crate::types::library::{LibraryBucket, LibraryExport, LibraryItem, LibraryItemState};
+use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use std::iter;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_exportlibrary() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    let newer_item = LibraryItem {
+        id: "id2".to_owned(),
+        mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        ..library_item.to_owned()
+    };
+    TestEnv::reset();
+    let (runtime, mut rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(
+                    None,
+                    vec![library_item.to_owned(), newer_item.to_owned()],
+                ),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::ExportLibrary),
+        })
+    });
+    let exports = iter::from_fn(|| rx.try_next().ok().flatten())
+        .filter_map(|event| match event {
+            RuntimeEvent::CoreEvent(Event::LibraryExported { export }) => Some(export),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exports,
+        vec![LibraryExport::new(vec![newer_item, library_item])],
+        "Library items exported newest first"
+    );
+    assert!(
+        STORAGE.read().unwrap().is_empty(),
+        "Nothing has been persisted"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
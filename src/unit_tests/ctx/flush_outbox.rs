@@ -0,0 +1,272 @@
+use crate::constants::OUTBOX_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, EnvError, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::api::{APIResult, SuccessResponse};
+use crate::types::library::{LibraryBucket, LibraryItem};
+use crate::types::outbox::OutboxBucket;
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
+use crate::types::True;
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
+};
+use chrono::prelude::TimeZone;
+use chrono::{Duration, Utc};
+use futures::{future, FutureExt};
+use std::any::Any;
+use std::cell::Cell;
+use stremio_derive::Model;
+
+fn test_profile() -> Profile {
+    Profile {
+        auth: Some(Auth {
+            key: AuthKey("auth_key".to_owned()),
+            user: User {
+                id: "user_id".to_owned(),
+                email: "user_email".to_owned(),
+                fb_id: None,
+                avatar: None,
+                last_modified: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                gdpr_consent: GDPRConsent {
+                    tos: true,
+                    privacy: true,
+                    marketing: true,
+                },
+            },
+        }),
+        ..Default::default()
+    }
+}
+
+fn test_outbox() -> OutboxBucket {
+    let mut outbox = OutboxBucket::new(Some("user_id".to_owned()));
+    outbox.merge_library_items(vec![LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: Default::default(),
+        name: "name".to_owned(),
+        r#type: "type".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }]);
+    outbox
+}
+
+#[test]
+fn actionctx_flushoutbox() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: test_profile(),
+                outbox: test_outbox(),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::FlushOutbox),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.outbox,
+        OutboxBucket::new(Some("user_id".to_owned())),
+        "Outbox emptied successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(OUTBOX_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<OutboxBucket>(&data).unwrap()
+                    == OutboxBucket::new(Some("user_id".to_owned()))
+            }),
+        "Outbox updated successfully in storage"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(0).unwrap().url.to_owned(),
+        "https://api.strem.io/api/datastorePut".to_owned(),
+        "datastorePut request has been sent"
+    );
+}
+
+#[test]
+fn actionctx_flushoutbox_offline() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                future::err(EnvError::Fetch("offline".to_owned())).boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: test_profile(),
+                outbox: test_outbox(),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::FlushOutbox),
+        })
+    });
+    let outbox = OutboxBucket {
+        attempts: 1,
+        retry_after: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0) + Duration::seconds(5)),
+        ..test_outbox()
+    };
+    assert_eq!(
+        runtime.model().unwrap().ctx.outbox,
+        outbox,
+        "Outbox kept the pending items in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(OUTBOX_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<OutboxBucket>(&data).unwrap() == outbox
+            }),
+        "Outbox updated successfully in storage"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+}
+
+#[test]
+fn actionctx_flushoutbox_restored_ctx() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    thread_local! {
+        static ONLINE: Cell<bool> = Cell::new(false);
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                if ONLINE.with(Cell::get) {
+                    future::ok(Box::new(APIResult::Ok {
+                        result: SuccessResponse { success: True {} },
+                    }) as Box<dyn Any>)
+                    .boxed_local()
+                } else {
+                    future::err(EnvError::Fetch("offline".to_owned())).boxed_local()
+                }
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx::new(
+                test_profile(),
+                LibraryBucket::new(
+                    Some("user_id".to_owned()),
+                    test_outbox().library_items.into_values().collect(),
+                ),
+            ),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RemoveFromLibrary("id".to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.outbox.uid,
+        Some("user_id".to_owned()),
+        "Outbox claimed by the logged in user"
+    );
+    assert!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .outbox
+            .library_items
+            .contains_key("id"),
+        "Failed push queued in the outbox"
+    );
+    ONLINE.with(|online| online.set(true));
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::FlushOutbox),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.outbox.library_items.is_empty(),
+        "Outbox flushed"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        2,
+        "Failed push retried once"
+    );
+}
@@ -0,0 +1,177 @@
+use crate::constants::{LIBRARY_EXPORT_VERSION, LIBRARY_RECENT_STORAGE_KEY};
+use crate::models::ctx::Ctx;
+use crate::models::ctx::{CtxError, OtherError};
+use crate::runtime::msg::{Action, ActionCtx, Event};
+use crate::runtime::{Effects, Runtime, RuntimeAction, RuntimeEvent};
+use crate::types::library::{LibraryBucket, LibraryExport, LibraryItem, LibraryItemState};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS, STORAGE};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use futures::channel::mpsc::Receiver;
+use std::iter;
+use stremio_derive::Model;
+
+fn events(rx: &mut Receiver<RuntimeEvent>) -> Vec<Event> {
+    iter::from_fn(|| rx.try_next().ok().flatten())
+        .filter_map(|event| match event {
+            RuntimeEvent::CoreEvent(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn actionctx_importlibrary() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    let updated_item = LibraryItem {
+        id: "id2".to_owned(),
+        ..library_item.to_owned()
+    };
+    let updated_item_imported = LibraryItem {
+        mtime: Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            times_watched: 1,
+            ..LibraryItemState::default()
+        },
+        ..updated_item.to_owned()
+    };
+    let skipped_item_imported = LibraryItem {
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            times_watched: 1,
+            ..LibraryItemState::default()
+        },
+        ..library_item.to_owned()
+    };
+    let added_item_imported = LibraryItem {
+        id: "id3".to_owned(),
+        ..library_item.to_owned()
+    };
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 4).and_hms_milli(0, 0, 0, 0);
+    let (runtime, mut rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(
+                    None,
+                    vec![library_item.to_owned(), updated_item.to_owned()],
+                ),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::ImportLibrary(LibraryExport::new(vec![
+                updated_item_imported.to_owned(),
+                skipped_item_imported,
+                added_item_imported.to_owned(),
+            ]))),
+        })
+    });
+    let expected_library = LibraryBucket::new(
+        None,
+        vec![library_item, updated_item_imported, added_item_imported],
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.library,
+        expected_library,
+        "Newer library items imported in memory"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LIBRARY_RECENT_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<LibraryBucket>(data).unwrap()),
+        Some(expected_library),
+        "Newer library items imported in storage"
+    );
+    assert!(
+        events(&mut rx).iter().any(|event| matches!(
+            event,
+            Event::LibraryImported {
+                added: 1,
+                updated: 1,
+                skipped: 1,
+            }
+        )),
+        "Imported items counted"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_importlibrary_version_not_supported() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    let (runtime, mut rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::ImportLibrary(LibraryExport {
+                version: LIBRARY_EXPORT_VERSION + 1,
+                items: vec![library_item],
+            })),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.library.items.is_empty(),
+        "No library items imported in memory"
+    );
+    assert!(
+        STORAGE.read().unwrap().is_empty(),
+        "No library items imported in storage"
+    );
+    assert!(
+        events(&mut rx).iter().any(|event| matches!(
+            event,
+            Event::Error {
+                error: CtxError::Other(OtherError::LibraryExportVersionNotSupported),
+                ..
+            }
+        )),
+        "Unsupported version rejected"
+    );
+}
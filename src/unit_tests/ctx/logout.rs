@@ -1,10 +1,14 @@
-use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY, PROFILE_STORAGE_KEY};
+use crate::constants::{
+    LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY, PROFILE_STORAGE_KEY,
+    SEARCH_HISTORY_STORAGE_KEY,
+};
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
 use crate::runtime::{Effects, Env, Runtime, RuntimeAction, TryEnvFuture};
 use crate::types::api::{APIResult, SuccessResponse};
 use crate::types::library::LibraryBucket;
 use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
+use crate::types::search_history::SearchHistoryBucket;
 use crate::types::True;
 use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
@@ -59,6 +63,8 @@ fn actionctx_logout() {
         uid: profile.uid(),
         ..Default::default()
     };
+    let mut search_history = SearchHistoryBucket::new(profile.uid());
+    search_history.record("query", TestEnv::now());
     TestEnv::reset();
     *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
     STORAGE.write().unwrap().insert(
@@ -73,11 +79,16 @@ fn actionctx_logout() {
         LIBRARY_STORAGE_KEY.to_owned(),
         serde_json::to_string(&LibraryBucket::new(profile.uid(), vec![])).unwrap(),
     );
+    STORAGE.write().unwrap().insert(
+        SEARCH_HISTORY_STORAGE_KEY.to_owned(),
+        serde_json::to_string(&search_history).unwrap(),
+    );
     let (runtime, _rx) = Runtime::<TestEnv, _>::new(
         TestModel {
             ctx: Ctx {
                 profile,
                 library,
+                search_history,
                 ..Default::default()
             },
         },
@@ -100,6 +111,11 @@ fn actionctx_logout() {
         Default::default(),
         "library updated successfully in memory"
     );
+    assert_eq!(
+        runtime.model().unwrap().ctx.search_history,
+        Default::default(),
+        "search history updated successfully in memory"
+    );
     assert!(
         STORAGE
             .read()
@@ -130,6 +146,16 @@ fn actionctx_logout() {
             }),
         "library updated successfully in storage"
     );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(SEARCH_HISTORY_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<SearchHistoryBucket>(&data).unwrap() == Default::default()
+            }),
+        "search history updated successfully in storage"
+    );
     assert_eq!(
         REQUESTS.read().unwrap().len(),
         1,
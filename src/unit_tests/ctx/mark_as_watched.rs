@@ -0,0 +1,242 @@
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::{MetaItem, SeriesInfo, Video};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use stremio_derive::Model;
+
+fn library_item() -> LibraryItem {
+    LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            video_id: Some("id:1:2".to_owned()),
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }
+}
+
+fn video(season: u32, episode: u32) -> Video {
+    Video {
+        id: format!("id:{}:{}", season, episode),
+        title: "title".to_owned(),
+        released: None,
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: Some(SeriesInfo { season, episode }),
+        trailer_streams: vec![],
+    }
+}
+
+#[test]
+fn actionctx_markaswatched() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = library_item();
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MarkAsWatched {
+                id: library_item.id.to_owned(),
+                watched: true,
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id),
+        Some(&LibraryItem {
+            mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+            state: LibraryItemState {
+                last_watched: Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+                times_watched: 1,
+                flagged_watched: 1,
+                ..library_item.state.to_owned()
+            },
+            ..library_item.to_owned()
+        }),
+        "Library item marked as watched in memory"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_markaswatched_not_watched() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        state: LibraryItemState {
+            time_offset: 10,
+            time_watched: 10,
+            times_watched: 3,
+            flagged_watched: 1,
+            watched: Some("id:1:1:2:eJyTZwAAAEAAIA==".to_owned()),
+            ..library_item().state
+        },
+        ..library_item()
+    };
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MarkAsWatched {
+                id: library_item.id.to_owned(),
+                watched: false,
+            }),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id),
+        Some(&LibraryItem {
+            mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+            state: LibraryItemState {
+                time_offset: 0,
+                time_watched: 0,
+                flagged_watched: 0,
+                ..library_item.state.to_owned()
+            },
+            ..library_item.to_owned()
+        }),
+        "Current video unmarked, times watched and watched videos kept in memory"
+    );
+}
+
+#[test]
+fn actionctx_markseasonaswatched() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = library_item();
+    let meta_item = MetaItem {
+        id: library_item.id.to_owned(),
+        videos: vec![video(1, 1), video(1, 2), video(2, 1)],
+        ..Default::default()
+    };
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MarkSeasonAsWatched {
+                meta_item: meta_item.to_owned(),
+                season: 1,
+                watched: true,
+            }),
+        })
+    });
+    let state = runtime
+        .model()
+        .unwrap()
+        .ctx
+        .library
+        .items
+        .get(&library_item.id)
+        .unwrap()
+        .state
+        .to_owned();
+    let watched = state.watched_bitfield(&meta_item.videos);
+    assert!(
+        watched.get_video("id:1:1") && watched.get_video("id:1:2") && !watched.get_video("id:2:1"),
+        "Season videos marked as watched"
+    );
+    assert_eq!(state.times_watched, 2, "Times watched updated");
+    assert_eq!(state.flagged_watched, 1, "Current video flagged as watched");
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::MarkVideoAsWatched {
+                meta_item: meta_item.to_owned(),
+                video_id: "id:1:2".to_owned(),
+                watched: false,
+            }),
+        })
+    });
+    let state = runtime
+        .model()
+        .unwrap()
+        .ctx
+        .library
+        .items
+        .get(&library_item.id)
+        .unwrap()
+        .state
+        .to_owned();
+    let watched = state.watched_bitfield(&meta_item.videos);
+    assert!(
+        watched.get_video("id:1:1") && !watched.get_video("id:1:2"),
+        "Video marked as not watched"
+    );
+    assert_eq!(state.times_watched, 1, "Times watched updated");
+    assert_eq!(
+        state.flagged_watched, 0,
+        "Current video not flagged as watched"
+    );
+}
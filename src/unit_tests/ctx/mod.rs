@@ -1,11 +1,21 @@
 mod add_to_library;
 mod authenticate;
+mod clear_search_history;
+mod export_library;
+mod flush_outbox;
+mod import_library;
 mod install_addon;
 mod logout;
+mod mark_as_watched;
 mod pull_addons_from_api;
+mod pull_user_from_api;
+mod purge_library_items;
 mod push_addons_to_api;
+mod push_user_to_api;
 mod remove_from_library;
+mod restore_library_item;
 mod rewind_library_item;
+mod set_library_item_notifications;
 mod sync_library_with_api;
 mod uninstall_addon;
 mod update_settings;
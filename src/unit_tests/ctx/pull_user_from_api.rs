@@ -0,0 +1,250 @@
+use crate::constants::PROFILE_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::api::APIResult;
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
+};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_pulluserfromapi() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PullUserFromAPI),
+        })
+    });
+    assert!(
+        runtime.model().unwrap().ctx.profile.auth.is_none(),
+        "profile not updated in memory"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_pulluserfromapi_with_user() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/getUser"
+                && method == "POST"
+                && body == "{\"type\":\"GetUser\",\"authKey\":\"auth_key\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: User {
+                        id: "user_id".to_owned(),
+                        email: "user_email".to_owned(),
+                        fb_id: None,
+                        avatar: Some("avatar".to_owned()),
+                        last_modified: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+                        date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                        gdpr_consent: GDPRConsent {
+                            tos: true,
+                            privacy: true,
+                            marketing: true,
+                        },
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let remote_user = User {
+        id: "user_id".to_owned(),
+        email: "user_email".to_owned(),
+        fb_id: None,
+        avatar: Some("avatar".to_owned()),
+        last_modified: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        gdpr_consent: GDPRConsent {
+            tos: true,
+            privacy: true,
+            marketing: true,
+        },
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                            date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: false,
+                            },
+                        },
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PullUserFromAPI),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .profile
+            .auth
+            .as_ref()
+            .map(|auth| auth.user.to_owned()),
+        Some(remote_user.to_owned()),
+        "user updated successfully in memory"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(PROFILE_STORAGE_KEY)
+            .map_or(false, |data| {
+                serde_json::from_str::<Profile>(&data)
+                    .unwrap()
+                    .auth
+                    .map(|auth| auth.user)
+                    == Some(remote_user.to_owned())
+            }),
+        "user updated successfully in storage"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(0).unwrap().url,
+        "https://api.strem.io/api/getUser".to_owned(),
+        "getUser request has been sent"
+    );
+}
+
+#[test]
+fn actionctx_pulluserfromapi_with_older_user() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/getUser" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: User {
+                        id: "user_id".to_owned(),
+                        email: "user_email".to_owned(),
+                        fb_id: None,
+                        avatar: None,
+                        last_modified: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                        date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                        gdpr_consent: GDPRConsent::default(),
+                    },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let local_user = User {
+        id: "user_id".to_owned(),
+        email: "user_email".to_owned(),
+        fb_id: None,
+        avatar: Some("avatar".to_owned()),
+        last_modified: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        gdpr_consent: GDPRConsent {
+            tos: true,
+            privacy: true,
+            marketing: true,
+        },
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: local_user.to_owned(),
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PullUserFromAPI),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .profile
+            .auth
+            .as_ref()
+            .map(|auth| auth.user.to_owned()),
+        Some(local_user),
+        "user not updated in memory"
+    );
+    assert!(
+        STORAGE.read().unwrap().get(PROFILE_STORAGE_KEY).is_none(),
+        "profile not updated in storage"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+}
@@ -0,0 +1,112 @@
+use crate::constants::{LIBRARY_RECENT_STORAGE_KEY, LIBRARY_STORAGE_KEY};
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS, STORAGE};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_purgelibraryitems() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: true,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    let recently_removed_item = LibraryItem {
+        id: "id2".to_owned(),
+        mtime: Utc.ymd(2020, 3, 1).and_hms_milli(0, 0, 0, 0),
+        ..library_item.to_owned()
+    };
+    let watched_item = LibraryItem {
+        id: "id3".to_owned(),
+        state: LibraryItemState {
+            overall_time_watched: 120_000,
+            ..LibraryItemState::default()
+        },
+        ..library_item.to_owned()
+    };
+    let not_removed_item = LibraryItem {
+        id: "id4".to_owned(),
+        removed: false,
+        ..library_item.to_owned()
+    };
+    let library_items = vec![
+        library_item.to_owned(),
+        recently_removed_item,
+        watched_item,
+        not_removed_item,
+    ];
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 3, 2).and_hms_milli(0, 0, 0, 0);
+    STORAGE.write().unwrap().insert(
+        LIBRARY_RECENT_STORAGE_KEY.to_owned(),
+        serde_json::to_string(&LibraryBucket::new(None, library_items.to_owned())).unwrap(),
+    );
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, library_items.to_owned()),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PurgeLibraryItems),
+        })
+    });
+    let expected_library = LibraryBucket::new(
+        None,
+        library_items
+            .into_iter()
+            .filter(|item| item.id != library_item.id)
+            .collect(),
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.library,
+        expected_library,
+        "Long removed unsynced item purged in memory"
+    );
+    assert_eq!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LIBRARY_RECENT_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<LibraryBucket>(data).unwrap()),
+        Some(expected_library),
+        "Long removed unsynced item purged in storage"
+    );
+    assert!(
+        STORAGE
+            .read()
+            .unwrap()
+            .get(LIBRARY_STORAGE_KEY)
+            .map(|data| serde_json::from_str::<LibraryBucket>(data).unwrap())
+            .iter()
+            .all(|bucket| bucket.items.is_empty()),
+        "No other library items in storage"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
@@ -0,0 +1,108 @@
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::api::{APIResult, SuccessResponse};
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, User};
+use crate::types::True;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_pushusertoapi() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PushUserToAPI),
+        })
+    });
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn actionctx_pushusertoapi_with_user() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/saveUser" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                            date_registered: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: false,
+                            },
+                        },
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::PushUserToAPI),
+        })
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(0).unwrap().to_owned(),
+        Request {
+            url: "https://api.strem.io/api/saveUser".to_owned(),
+            method: "POST".to_owned(),
+            body: "{\"type\":\"SaveUser\",\"authKey\":\"auth_key\",\"_id\":\"user_id\",\"email\":\"user_email\",\"fbId\":null,\"avatar\":null,\"lastModified\":\"2020-01-01T00:00:00Z\",\"dateRegistered\":\"2020-01-01T00:00:00Z\",\"gdpr_consent\":{\"tos\":true,\"privacy\":true,\"marketing\":false}}"
+                .to_owned(),
+            ..Default::default()
+        },
+        "saveUser request has been sent"
+    );
+}
@@ -93,6 +93,7 @@ fn actionctx_removefromlibrary() {
                     items: vec![("id".to_owned(), library_item.to_owned())]
                         .into_iter()
                         .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
@@ -177,6 +178,7 @@ fn actionctx_removefromlibrary_not_added() {
                     items: vec![("id".to_owned(), library_item.to_owned())]
                         .into_iter()
                         .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
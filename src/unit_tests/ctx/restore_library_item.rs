@@ -0,0 +1,71 @@
+use crate::models::ctx::Ctx;
+use crate::runtime::msg::{Action, ActionCtx};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::unit_tests::{TestEnv, NOW, REQUESTS};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use stremio_derive::Model;
+
+#[test]
+fn actionctx_restorelibraryitem() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: true,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            time_offset: 10,
+            times_watched: 2,
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item.to_owned()]),
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::RestoreLibraryItem(library_item.id.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get(&library_item.id),
+        Some(&LibraryItem {
+            removed: false,
+            mtime: Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+            ..library_item.to_owned()
+        }),
+        "Library item restored with its state and ctime in memory"
+    );
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
@@ -99,6 +99,7 @@ fn actionctx_rewindlibraryitem() {
                     items: vec![("id".to_owned(), library_item.to_owned())]
                         .into_iter()
                         .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
@@ -186,6 +187,7 @@ fn actionctx_rewindlibraryitem_not_added() {
                     items: vec![("id".to_owned(), library_item.to_owned())]
                         .into_iter()
                         .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
@@ -10,11 +10,12 @@ use crate::unit_tests::{
     default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS, STORAGE,
 };
 use chrono::prelude::TimeZone;
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use futures::{future, FutureExt};
 use lazy_static::lazy_static;
 use serde::Deserialize;
 use std::any::Any;
+use std::iter;
 use stremio_derive::Model;
 
 #[test]
@@ -260,6 +261,7 @@ fn actionctx_synclibrarywithapi_with_user() {
                     ]
                     .into_iter()
                     .collect(),
+                    last_synced: None,
                 },
                 ..Default::default()
             },
@@ -296,6 +298,7 @@ fn actionctx_synclibrarywithapi_with_user() {
             ]
             .into_iter()
             .collect(),
+            last_synced: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
         },
         "library updated successfully in memory"
     );
@@ -305,17 +308,20 @@ fn actionctx_synclibrarywithapi_with_user() {
             .unwrap()
             .get(LIBRARY_RECENT_STORAGE_KEY)
             .map(|data| serde_json::from_str::<LibraryBucket>(&data).unwrap()),
-        Some(LibraryBucket::new(
-            Some("user_id".to_owned()),
-            vec![
-                REMOTE_ONLY_ITEM.to_owned(),
-                LOCAL_ONLY_ITEM.to_owned(),
-                REMOTE_NEWER_ITEM.to_owned(),
-                LOCAL_NEWER_ITEM.to_owned(),
-                LOCAL_NOT_WATCHED_ITEM.to_owned(),
-                LOCAL_WATCHED_ITEM.to_owned(),
-            ]
-        )),
+        Some(LibraryBucket {
+            last_synced: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            ..LibraryBucket::new(
+                Some("user_id".to_owned()),
+                vec![
+                    REMOTE_ONLY_ITEM.to_owned(),
+                    LOCAL_ONLY_ITEM.to_owned(),
+                    REMOTE_NEWER_ITEM.to_owned(),
+                    LOCAL_NEWER_ITEM.to_owned(),
+                    LOCAL_NOT_WATCHED_ITEM.to_owned(),
+                    LOCAL_WATCHED_ITEM.to_owned(),
+                ]
+            )
+        }),
         "Library recent slot updated successfully in storage"
     );
     assert_eq!(
@@ -410,3 +416,412 @@ fn actionctx_synclibrarywithapi_with_user_empty_library() {
         "datastoreMeta request has been sent"
     );
 }
+
+#[test]
+fn actionctx_synclibrarywithapi_with_user_incremental() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn library_item(id: String, mtime: DateTime<Utc>) -> LibraryItem {
+        LibraryItem {
+            id,
+            r#type: "type".to_owned(),
+            name: "name".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            mtime,
+            state: Default::default(),
+            behavior_hints: Default::default(),
+        }
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match &request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastoreMeta" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: vec![LibraryItemModified(
+                        "synced".to_owned(),
+                        Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                    )],
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    #[derive(Deserialize)]
+    struct Body {
+        changes: Vec<LibraryItem>,
+    }
+    let local_items = (0..150)
+        .map(|index| {
+            library_item(
+                format!("local_changed{}", index),
+                Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0),
+            )
+        })
+        .chain(iter::once(library_item(
+            "synced".to_owned(),
+            Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        )))
+        .collect::<Vec<_>>();
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: TestEnv::now(),
+                            date_registered: TestEnv::now(),
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: true,
+                            },
+                        },
+                    }),
+                    ..Default::default()
+                },
+                library: LibraryBucket {
+                    last_synced: Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+                    ..LibraryBucket::new(Some("user_id".to_owned()), local_items)
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SyncLibraryWithAPI),
+        })
+    });
+    let requests = REQUESTS.read().unwrap();
+    assert_eq!(requests.len(), 3, "Three requests have been sent");
+    let pushed_ids = requests
+        .iter()
+        .skip(1)
+        .map(|request| {
+            assert_eq!(
+                request.url, "https://api.strem.io/api/datastorePut",
+                "datastorePut request has been sent"
+            );
+            serde_json::from_str::<Body>(&request.body)
+                .unwrap()
+                .changes
+                .into_iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pushed_ids.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![100, 50],
+        "Changed items pushed in batches"
+    );
+    assert!(
+        pushed_ids
+            .iter()
+            .flatten()
+            .all(|id| id.starts_with("local_changed")),
+        "Only items changed since last sync pushed"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.library.last_synced,
+        Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+        "Last synced mtime not moved by local mtimes"
+    );
+}
+
+#[test]
+fn actionctx_synclibrarywithapi_with_user_remote_watermark() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn library_item(id: &str, mtime: DateTime<Utc>) -> LibraryItem {
+        LibraryItem {
+            id: id.to_owned(),
+            r#type: "type".to_owned(),
+            name: "name".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            mtime,
+            state: Default::default(),
+            behavior_hints: Default::default(),
+        }
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match &request {
+            Request { url, .. } if url == "https://api.strem.io/api/datastoreMeta" => {
+                future::ok(Box::new(APIResult::Ok {
+                    result: vec![
+                        // pushed by this device before the last sync
+                        LibraryItemModified(
+                            "own".to_owned(),
+                            Utc.ymd(2020, 1, 1).and_hms_milli(12, 0, 0, 0),
+                        ),
+                        // edited offline by another device and pushed after the last sync
+                        LibraryItemModified(
+                            "offline".to_owned(),
+                            Utc.ymd(2020, 1, 1).and_hms_milli(11, 30, 0, 0),
+                        ),
+                        // edited by a device with a clock behind the watermark
+                        LibraryItemModified(
+                            "behind".to_owned(),
+                            Utc.ymd(2020, 1, 1).and_hms_milli(9, 30, 0, 0),
+                        ),
+                        // older than the watermark, but missing locally
+                        LibraryItemModified(
+                            "missing".to_owned(),
+                            Utc.ymd(2020, 1, 1).and_hms_milli(9, 0, 0, 0),
+                        ),
+                    ],
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, body, .. } if url == "https://api.strem.io/api/datastoreGet" => {
+                #[derive(Deserialize)]
+                struct Body {
+                    ids: Vec<String>,
+                }
+                let mut ids = serde_json::from_str::<Body>(body).unwrap().ids;
+                ids.sort();
+                assert_eq!(ids, vec!["behind", "missing", "offline"], "Pulled ids");
+                future::ok(Box::new(APIResult::Ok {
+                    result: vec![
+                        library_item("behind", Utc.ymd(2020, 1, 1).and_hms_milli(9, 30, 0, 0)),
+                        library_item("missing", Utc.ymd(2020, 1, 1).and_hms_milli(9, 0, 0, 0)),
+                        library_item("offline", Utc.ymd(2020, 1, 1).and_hms_milli(11, 30, 0, 0)),
+                    ],
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request.to_owned()),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            ..User::default()
+                        },
+                    }),
+                    ..Default::default()
+                },
+                library: LibraryBucket {
+                    // the previous sync only saw remote mtimes up to 10:00
+                    last_synced: Some(Utc.ymd(2020, 1, 1).and_hms_milli(10, 0, 0, 0)),
+                    ..LibraryBucket::new(
+                        Some("user_id".to_owned()),
+                        vec![
+                            library_item("own", Utc.ymd(2020, 1, 1).and_hms_milli(12, 0, 0, 0)),
+                            library_item("offline", Utc.ymd(2020, 1, 1).and_hms_milli(10, 0, 0, 0)),
+                            library_item("behind", Utc.ymd(2020, 1, 1).and_hms_milli(9, 0, 0, 0)),
+                        ],
+                    )
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::SyncLibraryWithAPI),
+        })
+    });
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://api.strem.io/api/datastoreMeta",
+            "https://api.strem.io/api/datastoreGet"
+        ],
+        "Only pull request has been sent"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get("offline")
+            .map(|item| item.mtime),
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(11, 30, 0, 0)),
+        "Item edited offline by another device pulled"
+    );
+    assert!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .contains_key("missing"),
+        "Item missing locally pulled"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get("behind")
+            .map(|item| item.mtime),
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(9, 30, 0, 0)),
+        "Item edited by a device with a clock behind pulled"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.library.last_synced,
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(12, 0, 0, 0)),
+        "Last synced mtime is the newest remote mtime"
+    );
+}
+
+#[test]
+fn ctx_storage_loaded_effects() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    TestEnv::reset();
+    let ctx = Ctx::new(Profile::default(), LibraryBucket::default());
+    let effects = ctx.storage_loaded_effects::<TestEnv>();
+    let (_runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, effects, 1000);
+    TestEnv::run(|| {});
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests have been sent"
+    );
+}
+
+#[test]
+fn ctx_storage_loaded_effects_with_user() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    lazy_static! {
+        static ref LOCAL_ITEM: LibraryItem = LibraryItem {
+            id: "id".to_owned(),
+            r#type: "type".to_owned(),
+            name: "name".to_owned(),
+            poster: None,
+            poster_shape: Default::default(),
+            removed: false,
+            temp: false,
+            ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+            state: Default::default(),
+            behavior_hints: Default::default(),
+        };
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match &request {
+            Request {
+                url, method, body, ..
+            } if url == "https://api.strem.io/api/datastoreMeta"
+                && method == "POST"
+                && body == "{\"authKey\":\"auth_key\",\"collection\":\"libraryItem\"}" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: Vec::<LibraryItemModified>::new(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/datastorePut" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let ctx = Ctx::new(
+        Profile {
+            auth: Some(Auth {
+                key: AuthKey("auth_key".to_owned()),
+                user: User {
+                    id: "user_id".to_owned(),
+                    email: "user_email".to_owned(),
+                    fb_id: None,
+                    avatar: None,
+                    last_modified: TestEnv::now(),
+                    date_registered: TestEnv::now(),
+                    gdpr_consent: GDPRConsent {
+                        tos: true,
+                        privacy: true,
+                        marketing: true,
+                    },
+                },
+            }),
+            ..Default::default()
+        },
+        LibraryBucket::new(Some("user_id".to_owned()), vec![LOCAL_ITEM.to_owned()]),
+    );
+    let effects = ctx.storage_loaded_effects::<TestEnv>();
+    let (_runtime, _rx) = Runtime::<TestEnv, _>::new(TestModel { ctx }, effects, 1000);
+    TestEnv::run(|| {});
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "https://api.strem.io/api/datastoreMeta",
+            "https://api.strem.io/api/datastorePut"
+        ],
+        "Library synced once loaded from storage"
+    );
+}
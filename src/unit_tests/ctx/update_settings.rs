@@ -1,9 +1,17 @@
 use crate::constants::PROFILE_STORAGE_KEY;
 use crate::models::ctx::Ctx;
 use crate::runtime::msg::{Action, ActionCtx};
-use crate::runtime::{Effects, Runtime, RuntimeAction};
-use crate::types::profile::{Profile, Settings};
-use crate::unit_tests::{TestEnv, REQUESTS, STORAGE};
+use crate::runtime::{Effects, Env, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::api::{APIResult, SuccessResponse};
+use crate::types::profile::{Auth, AuthKey, GDPRConsent, Profile, Settings, User};
+use crate::types::True;
+use crate::unit_tests::{
+    default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS, STORAGE,
+};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use futures::{future, FutureExt};
+use std::any::Any;
 use stremio_derive::Model;
 
 #[test]
@@ -105,3 +113,97 @@ fn actionctx_updatesettings_not_changed() {
         "No requests have been sent"
     );
 }
+
+#[test]
+fn actionctx_updatesettings_with_user() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://api.strem.io/api/settingsSet" && method == "POST" =>
+            {
+                future::ok(Box::new(APIResult::Ok {
+                    result: SuccessResponse { success: True {} },
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let settings = Settings {
+        subtitles_language: "bg".to_string(),
+        subtitles_size: 150,
+        ..Settings::default()
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    auth: Some(Auth {
+                        key: AuthKey("auth_key".to_owned()),
+                        user: User {
+                            id: "user_id".to_owned(),
+                            email: "user_email".to_owned(),
+                            fb_id: None,
+                            avatar: None,
+                            last_modified: TestEnv::now(),
+                            date_registered: TestEnv::now(),
+                            gdpr_consent: GDPRConsent {
+                                tos: true,
+                                privacy: true,
+                                marketing: true,
+                            },
+                        },
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Ctx(ActionCtx::UpdateSettings(settings.to_owned())),
+        })
+    });
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.settings,
+        settings,
+        "Settings updated successfully in memory"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.profile.settings_last_modified,
+        Some(TestEnv::now()),
+        "Settings last modified updated successfully in memory"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().get(0).unwrap().url,
+        "https://api.strem.io/api/settingsSet".to_owned(),
+        "settingsSet request has been sent"
+    );
+    assert!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .body
+            .contains("\"lastModified\":\"2020-01-01T00:00:00Z\""),
+        "settingsSet request contains last modified"
+    );
+}
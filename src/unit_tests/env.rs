@@ -1,11 +1,14 @@
 use crate::models::ctx::Ctx;
 use crate::models::streaming_server::StreamingServer;
 use crate::runtime::{Env, EnvFuture, TryEnvFuture};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use futures::executor::{LocalPool, LocalSpawner};
+use futures::task::{LocalSpawnExt, Poll, Waker};
 use futures::{future, Future, FutureExt, TryFutureExt};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::any::Any;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::ops::Fn;
@@ -19,6 +22,12 @@ lazy_static! {
     pub static ref NOW: RwLock<DateTime<Utc>> = RwLock::new(Utc::now());
 }
 
+thread_local! {
+    static EXECUTOR: RefCell<LocalPool> = RefCell::new(LocalPool::new());
+    static SPAWNER: LocalSpawner = EXECUTOR.with(|executor| executor.borrow().spawner());
+    static SLEEPS: RefCell<Vec<Waker>> = RefCell::new(vec![]);
+}
+
 pub type FetchHandler = Box<dyn Fn(Request) -> TryEnvFuture<Box<dyn Any>> + Send + Sync + 'static>;
 
 #[derive(Default, Debug, Clone, PartialEq)]
@@ -55,9 +64,15 @@ impl TestEnv {
         *NOW.write().unwrap() = Utc::now();
     }
     pub fn run<F: FnOnce()>(runnable: F) {
-        tokio_current_thread::block_on_all(future::lazy(|_| {
-            runnable();
-        }))
+        runnable();
+        EXECUTOR.with(|executor| executor.borrow_mut().run_until_stalled());
+    }
+    // Moves the fake clock forward and runs everything that became ready, e.g. sleeps
+    pub fn advance_clock(duration: Duration) {
+        let now = TestEnv::now();
+        *NOW.write().unwrap() = now + duration;
+        SLEEPS.with(|sleeps| sleeps.borrow_mut().drain(..).for_each(Waker::wake));
+        TestEnv::run(|| {});
     }
 }
 
@@ -98,11 +113,23 @@ impl Env for TestEnv {
     where
         F: Future<Output = ()> + 'static,
     {
-        tokio_current_thread::spawn(future);
+        SPAWNER.with(|spawner| spawner.spawn_local(future).unwrap());
     }
     fn now() -> DateTime<Utc> {
         *NOW.read().unwrap()
     }
+    fn sleep(duration: Duration) -> EnvFuture<()> {
+        let deadline = TestEnv::now() + duration;
+        future::poll_fn(move |cx| {
+            if TestEnv::now() >= deadline {
+                Poll::Ready(())
+            } else {
+                SLEEPS.with(|sleeps| sleeps.borrow_mut().push(cx.waker().to_owned()));
+                Poll::Pending
+            }
+        })
+        .boxed_local()
+    }
     fn flush_analytics() -> EnvFuture<()> {
         future::ready(()).boxed_local()
     }
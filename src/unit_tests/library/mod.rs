@@ -0,0 +1 @@
+mod trakt_history;
@@ -0,0 +1,110 @@
+use crate::types::library::{
+    from_trakt_history, to_trakt_history, LibraryBucket, LibraryItem, LibraryItemState,
+    TraktHistoryEntry,
+};
+use crate::types::resource::{SeriesInfo, Video};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use std::collections::HashMap;
+
+const HISTORY_FIXTURE: &str = r#"[
+    {"type":"movie","imdb_id":"tt1","title":"movie","watched_at":"2020-01-01T00:00:00Z"},
+    {"type":"episode","imdb_id":"tt2","title":"series","season":1,"episode":1,"watched_at":"2020-01-02T00:00:00Z"},
+    {"type":"episode","imdb_id":"tt2","title":"series","season":1,"episode":2,"watched_at":"2020-01-03T00:00:00Z"},
+    {"type":"movie","imdb_id":"kitsu:1","title":"anime","watched_at":"2020-01-01T00:00:00Z"}
+]"#;
+
+fn videos() -> Vec<Video> {
+    (1..=3)
+        .map(|episode| Video {
+            id: format!("tt2:1:{}", episode),
+            title: Default::default(),
+            released: None,
+            overview: None,
+            thumbnail: None,
+            streams: vec![],
+            series_info: Some(SeriesInfo { season: 1, episode }),
+            trailer_streams: vec![],
+        })
+        .collect()
+}
+
+#[test]
+fn trakt_history_round_trip() {
+    let entries = serde_json::from_str::<Vec<TraktHistoryEntry>>(HISTORY_FIXTURE).unwrap();
+    let now = Utc.ymd(2020, 2, 1).and_hms_milli(0, 0, 0, 0);
+    let videos = vec![("tt2".to_owned(), videos())]
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    let library_items = from_trakt_history(&LibraryBucket::default(), &entries, &videos, now);
+    assert_eq!(
+        library_items.len(),
+        2,
+        "Only IMDb history entries converted to library items"
+    );
+    let movie = library_items.iter().find(|item| item.id == "tt1").unwrap();
+    assert_eq!(movie.r#type, "movie", "Movie type converted");
+    assert_eq!(movie.state.times_watched, 1, "Movie marked as watched");
+    assert_eq!(
+        movie.state.last_watched,
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        "Movie last watched converted"
+    );
+    let series = library_items.iter().find(|item| item.id == "tt2").unwrap();
+    assert_eq!(series.r#type, "series", "Series type converted");
+    assert_eq!(series.state.times_watched, 2, "Episodes marked as watched");
+    assert_eq!(
+        series.ctime,
+        Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+        "Series ctime is the first watched episode"
+    );
+    let mut history = library_items
+        .iter()
+        .flat_map(|item| {
+            to_trakt_history(
+                item,
+                videos.get(&item.id).map(Vec::as_slice).unwrap_or_default(),
+            )
+        })
+        .map(|entry| entry.video_id())
+        .collect::<Vec<_>>();
+    history.sort();
+    assert_eq!(
+        history,
+        vec!["tt1", "tt2:1:1", "tt2:1:2"],
+        "Watched movie and episodes converted back to history entries"
+    );
+}
+
+#[test]
+fn trakt_history_skip_unchanged() {
+    let entries = serde_json::from_str::<Vec<TraktHistoryEntry>>(HISTORY_FIXTURE).unwrap();
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        name: "movie".to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            last_watched: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            times_watched: 1,
+            flagged_watched: 1,
+            ..LibraryItemState::default()
+        },
+        behavior_hints: Default::default(),
+    };
+    let library_items = from_trakt_history(
+        &LibraryBucket::new(None, vec![library_item]),
+        &entries[..1],
+        &HashMap::new(),
+        Utc.ymd(2020, 2, 1).and_hms_milli(0, 0, 0, 0),
+    );
+    assert!(
+        library_items.is_empty(),
+        "Already watched movie is not updated"
+    );
+}
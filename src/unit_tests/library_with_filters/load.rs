@@ -0,0 +1,171 @@
+use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::{
+    LibraryRequest, LibraryRequestPage, LibraryWithFilters, NotRemovedFilter, Selected, Sort,
+    SortOrder,
+};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::unit_tests::TestEnv;
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use stremio_derive::Model;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    library: LibraryWithFilters<NotRemovedFilter>,
+}
+
+fn library_item(id: &str, name: &str, day: u32, duration: u64, time_offset: u64) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, day).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            duration,
+            time_offset,
+            ..LibraryItemState::default()
+        },
+        name: name.to_owned(),
+        r#type: "movie".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }
+}
+
+fn test_model() -> TestModel {
+    TestModel {
+        ctx: Ctx {
+            library: LibraryBucket::new(
+                None,
+                vec![
+                    library_item("a", "Amélie", 1, 300, 50),
+                    library_item("b", "amelie 2", 3, 100, 30),
+                    library_item("c", "Zorro", 2, 200, 180),
+                ],
+            ),
+            ..Default::default()
+        },
+        library: Default::default(),
+    }
+}
+
+fn request(sort: Sort, order: Option<SortOrder>, search: Option<&str>) -> LibraryRequest {
+    LibraryRequest {
+        r#type: None,
+        sort,
+        order,
+        search: search.map(ToOwned::to_owned),
+        page: LibraryRequestPage::default(),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>, request: LibraryRequest) -> Vec<String> {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected { request })),
+        })
+    });
+    runtime
+        .model()
+        .unwrap()
+        .library
+        .catalog
+        .iter()
+        .map(|library_item| library_item.id.to_owned())
+        .collect()
+}
+
+#[test]
+fn library_with_filters_search() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    assert_eq!(
+        load(
+            &runtime,
+            request(Sort::DateAdded, Some(SortOrder::Ascending), Some("AMELIE"))
+        ),
+        vec!["a", "b"],
+        "Search is case and diacritic insensitive"
+    );
+    assert_eq!(
+        load(
+            &runtime,
+            request(Sort::DateAdded, Some(SortOrder::Ascending), Some("amél"))
+        ),
+        vec!["a", "b"],
+        "Search query diacritics ignored"
+    );
+    assert!(
+        load(
+            &runtime,
+            request(Sort::DateAdded, Some(SortOrder::Ascending), Some("batman"))
+        )
+        .is_empty(),
+        "No items match the search"
+    );
+}
+
+#[test]
+fn library_with_filters_sort() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    for (sort, ascending) in vec![
+        (Sort::DateAdded, vec!["a", "c", "b"]),
+        (Sort::Watched, vec!["a", "b", "c"]),
+        (Sort::Duration, vec!["b", "c", "a"]),
+    ] {
+        assert_eq!(
+            load(
+                &runtime,
+                request(sort.to_owned(), Some(SortOrder::Ascending), None)
+            ),
+            ascending,
+            "Sorted ascending"
+        );
+        assert_eq!(
+            load(&runtime, request(sort, Some(SortOrder::Descending), None)),
+            ascending.into_iter().rev().collect::<Vec<_>>(),
+            "Sorted descending"
+        );
+    }
+}
+
+#[test]
+fn library_with_filters_selectable_sorts() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    load(&runtime, request(Sort::Duration, None, Some("amelie")));
+    let model = runtime.model().unwrap();
+    let sorts = &model.library.selectable.sorts;
+    assert_eq!(sorts.len(), 12, "Every sort in both orders is selectable");
+    assert_eq!(
+        sorts.iter().filter(|sort| sort.selected).count(),
+        1,
+        "Only one sort is selected"
+    );
+    assert!(
+        sorts.iter().any(|sort| sort.selected
+            && sort.sort == Sort::Duration
+            && sort.order == SortOrder::Descending),
+        "Selected sort uses the default order"
+    );
+    assert!(
+        sorts.iter().all(|sort| sort.request
+            == request(
+                sort.sort.to_owned(),
+                Some(sort.order.to_owned()),
+                Some("amelie")
+            )),
+        "Sort requests keep the search"
+    );
+}
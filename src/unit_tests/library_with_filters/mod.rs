@@ -0,0 +1,2 @@
+mod load;
+mod toggle_notifications;
@@ -0,0 +1,102 @@
+use crate::models::ctx::Ctx;
+use crate::models::library_with_filters::{
+    LibraryRequest, LibraryRequestPage, LibraryWithFilters, NotRemovedFilter, Selected, Sort,
+};
+use crate::runtime::msg::{Action, ActionLibraryWithFilters, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::unit_tests::{TestEnv, NOW};
+use chrono::prelude::TimeZone;
+use chrono::{Duration, Utc};
+use stremio_derive::Model;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    library: LibraryWithFilters<NotRemovedFilter>,
+}
+
+fn toggle_notifications(runtime: &Runtime<TestEnv, TestModel>) -> (bool, bool) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::LibraryWithFilters(ActionLibraryWithFilters::ToggleNotifications(
+                "id".to_owned(),
+            )),
+        })
+    });
+    let model = runtime.model().unwrap();
+    (
+        model.ctx.library.items.get("id").unwrap().state.no_notif,
+        model.library.catalog.first().unwrap().state.no_notif,
+    )
+}
+
+#[test]
+fn library_with_filters_toggle_notifications() {
+    let library_item = LibraryItem {
+        id: "id".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            library: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::LibraryWithFilters(Selected {
+                request: LibraryRequest {
+                    r#type: None,
+                    sort: Sort::default(),
+                    order: None,
+                    search: None,
+                    page: LibraryRequestPage::default(),
+                },
+            })),
+        })
+    });
+    assert_eq!(
+        toggle_notifications(&runtime),
+        (true, true),
+        "Notifications disabled in ctx library and catalog"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get("id")
+            .unwrap()
+            .mtime,
+        Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+        "Library item updated through ctx"
+    );
+    TestEnv::advance_clock(Duration::days(1));
+    assert_eq!(
+        toggle_notifications(&runtime),
+        (false, false),
+        "Notifications enabled again"
+    );
+}
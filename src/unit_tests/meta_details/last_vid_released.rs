@@ -0,0 +1,133 @@
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{Descriptor, Manifest, ManifestResource, ResourcePath, ResourceResponse};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::profile::Profile;
+use crate::types::resource::{MetaItem, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW};
+use chrono::prelude::TimeZone;
+use chrono::{DateTime, Utc};
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+fn video(id: &str, released: DateTime<Utc>) -> Video {
+    Video {
+        id: id.to_owned(),
+        title: "title".to_owned(),
+        released: Some(released),
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: None,
+        trailer_streams: vec![],
+    }
+}
+
+#[test]
+fn meta_details_last_vid_released() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        meta_details: MetaDetails,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, .. }
+                if url == "https://last-vid-released.addon/meta/series/tt1.json" =>
+            {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        videos: vec![
+                            video("tt1:1:1", Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+                            video("tt1:1:2", Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+                            video("tt1:1:3", Utc.ymd(2020, 1, 9).and_hms_milli(0, 0, 0, 0)),
+                        ],
+                        ..Default::default()
+                    },
+                    cache_hints: Default::default(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState::default(),
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: Manifest {
+                            id: "id".to_owned(),
+                            version: Version::new(0, 0, 1),
+                            name: "name".to_owned(),
+                            contact_email: None,
+                            description: None,
+                            logo: None,
+                            background: None,
+                            types: vec!["series".to_owned()],
+                            resources: vec![ManifestResource::Short("meta".to_owned())],
+                            id_prefixes: None,
+                            catalogs: vec![],
+                            addon_catalogs: vec![],
+                            behavior_hints: Default::default(),
+                        },
+                        transport_url: Url::parse("https://last-vid-released.addon/manifest.json")
+                            .unwrap(),
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra("meta", "series", "tt1"),
+                stream_path: None,
+            })),
+        })
+    });
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .ctx
+            .library
+            .items
+            .get("tt1")
+            .and_then(|library_item| library_item.state.last_vid_released),
+        Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+        "Last video released set to the newest already released video"
+    );
+}
@@ -0,0 +1,4 @@
+mod last_vid_released;
+mod resource_cache;
+mod seasons_selectable;
+mod suggested_stream;
@@ -0,0 +1,237 @@
+use crate::models::common::Loadable;
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, EnvError, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, Manifest, ManifestResource, ResourceCacheHints, ResourcePath, ResourceResponse,
+};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItem;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use chrono::Duration;
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    meta_details: MetaDetails,
+}
+
+fn test_model(transport_url: &str) -> TestModel {
+    TestModel {
+        ctx: Ctx {
+            profile: Profile {
+                addons: vec![Descriptor {
+                    manifest: Manifest {
+                        id: "id".to_owned(),
+                        version: Version::new(0, 0, 1),
+                        name: "name".to_owned(),
+                        contact_email: None,
+                        description: None,
+                        logo: None,
+                        background: None,
+                        types: vec!["movie".to_owned()],
+                        resources: vec![ManifestResource::Short("meta".to_owned())],
+                        id_prefixes: None,
+                        catalogs: vec![],
+                        addon_catalogs: vec![],
+                        behavior_hints: Default::default(),
+                    },
+                    transport_url: Url::parse(transport_url).unwrap(),
+                    flags: Default::default(),
+                }],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        meta_details: Default::default(),
+    }
+}
+
+fn meta_item(name: &str) -> MetaItem {
+    MetaItem {
+        id: "tt1".to_owned(),
+        r#type: "movie".to_owned(),
+        name: name.to_owned(),
+        poster: None,
+        background: None,
+        logo: None,
+        popularity: None,
+        description: None,
+        release_info: None,
+        runtime: None,
+        released: None,
+        poster_shape: Default::default(),
+        videos: vec![],
+        links: vec![],
+        trailer_streams: vec![],
+        behavior_hints: Default::default(),
+    }
+}
+
+fn meta_response(
+    name: &'static str,
+    cache_hints: ResourceCacheHints,
+) -> impl Fn(Request) -> TryEnvFuture<Box<dyn Any>> {
+    move |request| match request {
+        Request { url, .. } if url.ends_with("/meta/movie/tt1.json") => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: meta_item(name),
+                cache_hints: cache_hints.to_owned(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn meta_error(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request {
+        Request { url, .. } if url.ends_with("/meta/movie/tt1.json") => {
+            future::err(EnvError::Fetch("network error".to_owned())).boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        });
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra("meta", "movie", "tt1"),
+                stream_path: None,
+            })),
+        });
+    });
+}
+
+#[test]
+fn meta_details_stale_while_revalidate() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(meta_response(
+        "cached",
+        ResourceCacheHints {
+            cache_max_age: Some(60),
+            stale_revalidate: Some(600),
+            stale_error: None,
+        },
+    ));
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        test_model("https://stale-revalidate.addon/manifest.json"),
+        Effects::none().unchanged(),
+        1000,
+    );
+    load(&runtime);
+    *FETCH_HANDLER.write().unwrap() =
+        Box::new(meta_response("revalidated", ResourceCacheHints::default()));
+    TestEnv::advance_clock(Duration::seconds(30));
+    load(&runtime);
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "Fresh response served from cache"
+    );
+    TestEnv::advance_clock(Duration::seconds(60));
+    load(&runtime);
+    assert!(
+        matches!(
+            &runtime.model().unwrap().meta_details.meta_items[0].content,
+            Loadable::Ready(meta_item) if meta_item.name == "cached"
+        ),
+        "Stale response served while revalidating"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        2,
+        "Revalidation request has been sent"
+    );
+    load(&runtime);
+    assert!(
+        matches!(
+            &runtime.model().unwrap().meta_details.meta_items[0].content,
+            Loadable::Ready(meta_item) if meta_item.name == "revalidated"
+        ),
+        "Revalidated response served"
+    );
+}
+
+#[test]
+fn meta_details_stale_on_error() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(meta_response(
+        "cached",
+        ResourceCacheHints {
+            cache_max_age: Some(60),
+            stale_revalidate: None,
+            stale_error: Some(600),
+        },
+    ));
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        test_model("https://stale-error.addon/manifest.json"),
+        Effects::none().unchanged(),
+        1000,
+    );
+    load(&runtime);
+    *FETCH_HANDLER.write().unwrap() = Box::new(meta_error);
+    TestEnv::advance_clock(Duration::seconds(120));
+    load(&runtime);
+    assert!(
+        matches!(
+            &runtime.model().unwrap().meta_details.meta_items[0].content,
+            Loadable::Ready(meta_item) if meta_item.name == "cached"
+        ),
+        "Stale response served on error"
+    );
+    TestEnv::advance_clock(Duration::seconds(600));
+    load(&runtime);
+    assert!(
+        runtime.model().unwrap().meta_details.meta_items[0]
+            .content
+            .is_err(),
+        "Error shown once the stale error window is over"
+    );
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        3,
+        "Three requests have been sent"
+    );
+}
+
+#[test]
+fn meta_details_error_without_stale_error() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(meta_response(
+        "cached",
+        ResourceCacheHints {
+            cache_max_age: Some(60),
+            stale_revalidate: None,
+            stale_error: None,
+        },
+    ));
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        test_model("https://no-stale-error.addon/manifest.json"),
+        Effects::none().unchanged(),
+        1000,
+    );
+    load(&runtime);
+    *FETCH_HANDLER.write().unwrap() = Box::new(meta_error);
+    TestEnv::advance_clock(Duration::seconds(120));
+    load(&runtime);
+    assert!(
+        runtime.model().unwrap().meta_details.meta_items[0]
+            .content
+            .is_err(),
+        "Error shown without a staleError hint"
+    );
+}
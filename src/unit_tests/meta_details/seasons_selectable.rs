@@ -0,0 +1,198 @@
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected, VideoStatus};
+use crate::runtime::msg::{Action, ActionLoad, ActionMetaDetails};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{Descriptor, Manifest, ManifestResource, ResourcePath, ResourceResponse};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::profile::Profile;
+use crate::types::resource::{MetaItem, SeriesInfo, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW};
+use chrono::prelude::TimeZone;
+use chrono::{DateTime, Utc};
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    meta_details: MetaDetails,
+}
+
+fn video(season: u32, episode: u32, released: DateTime<Utc>) -> Video {
+    Video {
+        id: format!("tt1:{}:{}", season, episode),
+        title: "title".to_owned(),
+        released: Some(released),
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: Some(SeriesInfo { season, episode }),
+        trailer_streams: vec![],
+    }
+}
+
+fn videos() -> Vec<Video> {
+    let released = Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0);
+    vec![
+        video(2, 1, released),
+        video(0, 1, released),
+        video(1, 2, released),
+        video(1, 1, released),
+        video(1, 3, Utc.ymd(2020, 2, 1).and_hms_milli(0, 0, 0, 0)),
+    ]
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request {
+        Request { url, .. } if url == "https://seasons.addon/meta/series/tt1.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    id: "tt1".to_owned(),
+                    r#type: "series".to_owned(),
+                    videos: videos(),
+                    ..Default::default()
+                },
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn seasons(runtime: &Runtime<TestEnv, TestModel>) -> Vec<(u32, bool)> {
+    runtime
+        .model()
+        .unwrap()
+        .meta_details
+        .seasons_selectable
+        .seasons
+        .iter()
+        .map(|season| (season.season, season.selected))
+        .collect()
+}
+
+#[test]
+fn meta_details_seasons_selectable() {
+    let mut state = LibraryItemState {
+        video_id: Some("tt1:2:1".to_owned()),
+        time_offset: 10,
+        ..LibraryItemState::default()
+    };
+    state.mark_videos_as_watched(
+        &videos(),
+        vec!["tt1:1:1"],
+        true,
+        Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+    );
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state,
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: Manifest {
+                            id: "id".to_owned(),
+                            version: Version::new(0, 0, 1),
+                            name: "name".to_owned(),
+                            contact_email: None,
+                            description: None,
+                            logo: None,
+                            background: None,
+                            types: vec!["series".to_owned()],
+                            resources: vec![ManifestResource::Short("meta".to_owned())],
+                            id_prefixes: None,
+                            catalogs: vec![],
+                            addon_catalogs: vec![],
+                            behavior_hints: Default::default(),
+                        },
+                        transport_url: Url::parse("https://seasons.addon/manifest.json").unwrap(),
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra("meta", "series", "tt1"),
+                stream_path: None,
+            })),
+        })
+    });
+    assert_eq!(
+        seasons(&runtime),
+        vec![(1, false), (2, true), (0, false)],
+        "Seasons ordered with specials last and the current video season selected"
+    );
+    let statuses = runtime
+        .model()
+        .unwrap()
+        .meta_details
+        .seasons_selectable
+        .seasons
+        .iter()
+        .flat_map(|season| season.videos.iter())
+        .map(|video| (video.video.id.to_owned(), video.status))
+        .collect::<Vec<_>>();
+    assert!(
+        statuses
+            == vec![
+                ("tt1:1:1".to_owned(), VideoStatus::Watched),
+                ("tt1:1:2".to_owned(), VideoStatus::Unwatched),
+                ("tt1:1:3".to_owned(), VideoStatus::Upcoming),
+                ("tt1:2:1".to_owned(), VideoStatus::InProgress),
+                ("tt1:0:1".to_owned(), VideoStatus::Unwatched),
+            ],
+        "Videos ordered by episode with their status"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::MetaDetails(ActionMetaDetails::SelectSeason(0)),
+        })
+    });
+    assert_eq!(
+        seasons(&runtime),
+        vec![(1, false), (2, false), (0, true)],
+        "Special season selected"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::MetaDetails(ActionMetaDetails::SelectSeason(5)),
+        })
+    });
+    assert_eq!(
+        seasons(&runtime),
+        vec![(1, false), (2, false), (0, true)],
+        "Unknown season not selected"
+    );
+}
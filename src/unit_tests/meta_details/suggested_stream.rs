@@ -0,0 +1,148 @@
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{Descriptor, Manifest, ManifestResource, ResourcePath, ResourceResponse};
+use crate::types::profile::Profile;
+use crate::types::resource::{MetaItem, Stream, StreamBehaviorHints, StreamSource};
+use crate::types::streams::{StreamsBucket, StreamsItem};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    meta_details: MetaDetails,
+}
+
+fn stream(yt_id: &str, binge_group: &str) -> Stream {
+    Stream {
+        source: StreamSource::YouTube {
+            yt_id: yt_id.to_owned(),
+        },
+        title: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: StreamBehaviorHints {
+            binge_group: Some(binge_group.to_owned()),
+            ..Default::default()
+        },
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request.url.as_str() {
+        "https://suggested.addon/meta/series/tt1.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    id: "tt1".to_owned(),
+                    r#type: "series".to_owned(),
+                    ..Default::default()
+                },
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        "https://suggested.addon/stream/series/tt1%3A1%3A1.json" => {
+            future::ok(Box::new(ResourceResponse::Streams {
+                streams: vec![stream("other", "other"), stream("played", "group")],
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        "https://suggested.addon/stream/series/tt1%3A1%3A2.json" => {
+            future::ok(Box::new(ResourceResponse::Streams {
+                streams: vec![stream("other", "other"), stream("next", "group")],
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>, video_id: &str) -> Option<Stream> {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::MetaDetails(Selected {
+                meta_path: ResourcePath::without_extra("meta", "series", "tt1"),
+                stream_path: Some(ResourcePath::without_extra("stream", "series", video_id)),
+            })),
+        })
+    });
+    runtime
+        .model()
+        .unwrap()
+        .meta_details
+        .suggested_stream
+        .to_owned()
+}
+
+#[test]
+fn meta_details_suggested_stream() {
+    let transport_url = Url::parse("https://suggested.addon/manifest.json").unwrap();
+    let mut streams = StreamsBucket::new(None);
+    streams.insert(StreamsItem {
+        meta_id: "tt1".to_owned(),
+        video_id: "tt1:1:1".to_owned(),
+        stream: stream("played", "group"),
+        transport_url: transport_url.to_owned(),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+    });
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: Manifest {
+                            id: "id".to_owned(),
+                            version: Version::new(0, 0, 1),
+                            name: "name".to_owned(),
+                            contact_email: None,
+                            description: None,
+                            logo: None,
+                            background: None,
+                            types: vec!["series".to_owned()],
+                            resources: vec![
+                                ManifestResource::Short("meta".to_owned()),
+                                ManifestResource::Short("stream".to_owned()),
+                            ],
+                            id_prefixes: None,
+                            catalogs: vec![],
+                            addon_catalogs: vec![],
+                            behavior_hints: Default::default(),
+                        },
+                        transport_url,
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                streams,
+                ..Default::default()
+            },
+            meta_details: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    assert_eq!(
+        load(&runtime, "tt1:1:1"),
+        Some(stream("played", "group")),
+        "Stored stream suggested for the same video"
+    );
+    assert_eq!(
+        load(&runtime, "tt1:1:2"),
+        Some(stream("next", "group")),
+        "Stream of the same binge group suggested for the next video"
+    );
+}
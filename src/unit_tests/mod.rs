@@ -1,5 +1,14 @@
 mod env;
 use env::*;
 
+mod addon_transport;
+mod catalog_with_filters;
 mod ctx;
+mod library;
+mod library_with_filters;
+mod meta_details;
+mod notifications;
+mod player;
+mod runtime;
+mod search;
 mod serde;
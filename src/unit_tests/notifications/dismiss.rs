@@ -0,0 +1,197 @@
+use crate::models::common::{Loadable, ResourceLoadable};
+use crate::models::ctx::Ctx;
+use crate::models::notifications::Notifications;
+use crate::runtime::msg::{Action, ActionNotifications};
+use crate::runtime::{Effects, Runtime, RuntimeAction};
+use crate::types::addon::{ResourcePath, ResourceRequest};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::{MetaItem, Video};
+use crate::unit_tests::{TestEnv, NOW};
+use chrono::prelude::TimeZone;
+use chrono::{DateTime, Utc};
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    notifications: Notifications,
+}
+
+fn library_item(id: &str) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            last_vid_released: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }
+}
+
+fn video(id: &str, released: DateTime<Utc>) -> Video {
+    Video {
+        id: id.to_owned(),
+        title: "title".to_owned(),
+        released: Some(released),
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: None,
+        trailer_streams: vec![],
+    }
+}
+
+fn test_model() -> TestModel {
+    TestModel {
+        ctx: Ctx {
+            library: LibraryBucket::new(None, vec![library_item("tt1"), library_item("tt2")]),
+            ..Default::default()
+        },
+        notifications: Notifications {
+            groups: vec![ResourceLoadable {
+                request: ResourceRequest::new(
+                    Url::parse("https://transport_url/manifest.json").unwrap(),
+                    ResourcePath::without_extra("catalog", "series", "last-videos"),
+                ),
+                content: Loadable::Ready(vec![
+                    MetaItem {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        videos: vec![
+                            video("tt1:1:1", Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+                            video("tt1:1:2", Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0)),
+                        ],
+                        ..Default::default()
+                    },
+                    MetaItem {
+                        id: "tt2".to_owned(),
+                        r#type: "series".to_owned(),
+                        videos: vec![video(
+                            "tt2:1:1",
+                            Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0),
+                        )],
+                        ..Default::default()
+                    },
+                ]),
+            }],
+            ..Default::default()
+        },
+    }
+}
+
+fn last_vid_released(runtime: &Runtime<TestEnv, TestModel>, id: &str) -> Option<DateTime<Utc>> {
+    runtime
+        .model()
+        .unwrap()
+        .ctx
+        .library
+        .items
+        .get(id)
+        .and_then(|library_item| library_item.state.last_vid_released)
+}
+
+fn notification_video_ids(runtime: &Runtime<TestEnv, TestModel>) -> Vec<String> {
+    runtime
+        .model()
+        .unwrap()
+        .notifications
+        .notification_items
+        .iter()
+        .map(|notification_item| notification_item.video.id.to_owned())
+        .collect()
+}
+
+fn group_videos_count(runtime: &Runtime<TestEnv, TestModel>) -> usize {
+    match &runtime.model().unwrap().notifications.groups[0].content {
+        Loadable::Ready(meta_items) => meta_items.iter().map(|item| item.videos.len()).sum(),
+        _ => 0,
+    }
+}
+
+#[test]
+fn notifications_dismiss() {
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 4).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Notifications(ActionNotifications::Dismiss("tt1".to_owned())),
+        })
+    });
+    assert_eq!(
+        last_vid_released(&runtime, "tt1"),
+        Some(Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0)),
+        "Last video released moved to the newest notified video"
+    );
+    assert_eq!(
+        last_vid_released(&runtime, "tt2"),
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        "Other library items not changed"
+    );
+    assert_eq!(
+        notification_video_ids(&runtime),
+        vec!["tt2:1:1"],
+        "Dismissed notifications removed"
+    );
+    assert_eq!(group_videos_count(&runtime), 3, "Groups not changed");
+}
+
+#[test]
+fn notifications_dismiss_all() {
+    TestEnv::reset();
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 4).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Notifications(ActionNotifications::DismissAll),
+        })
+    });
+    assert_eq!(
+        last_vid_released(&runtime, "tt1"),
+        Some(Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0)),
+        "Last video released moved forward"
+    );
+    assert_eq!(
+        last_vid_released(&runtime, "tt2"),
+        Some(Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+        "Last video released moved forward"
+    );
+    assert!(
+        notification_video_ids(&runtime).is_empty(),
+        "All notifications removed"
+    );
+    assert_eq!(group_videos_count(&runtime), 3, "Groups not changed");
+}
+
+#[test]
+fn notifications_dismiss_without_released() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Notifications(ActionNotifications::Dismiss("tt3".to_owned())),
+        })
+    });
+    assert_eq!(
+        last_vid_released(&runtime, "tt1"),
+        Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        "Library not changed"
+    );
+    assert_eq!(group_videos_count(&runtime), 3, "Groups not changed");
+}
@@ -0,0 +1,2 @@
+mod dismiss;
+mod notification_items;
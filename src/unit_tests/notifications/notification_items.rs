@@ -0,0 +1,182 @@
+use crate::models::ctx::Ctx;
+use crate::models::notifications::Notifications;
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, ExtraProp, Manifest, ManifestCatalog, ManifestExtra, ManifestResource,
+    ResourceResponse,
+};
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::profile::Profile;
+use crate::types::resource::{MetaItem, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW};
+use chrono::prelude::TimeZone;
+use chrono::{DateTime, Utc};
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    notifications: Notifications,
+}
+
+fn addon(transport_url: &str) -> Descriptor {
+    Descriptor {
+        manifest: Manifest {
+            id: transport_url.to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "name".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec!["series".to_owned()],
+            resources: vec![ManifestResource::Short("meta".to_owned())],
+            id_prefixes: None,
+            catalogs: vec![ManifestCatalog {
+                r#type: "series".to_owned(),
+                id: "last-videos".to_owned(),
+                name: None,
+                extra: ManifestExtra::Full {
+                    props: vec![ExtraProp {
+                        name: "lastVideosIds".to_owned(),
+                        is_required: true,
+                        options: None,
+                        options_limit: Default::default(),
+                    }],
+                },
+            }],
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse(transport_url).unwrap(),
+        flags: Default::default(),
+    }
+}
+
+fn library_item(id: &str) -> LibraryItem {
+    LibraryItem {
+        id: id.to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            last_vid_released: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    }
+}
+
+fn video(id: &str, released: DateTime<Utc>) -> Video {
+    Video {
+        id: id.to_owned(),
+        title: "title".to_owned(),
+        released: Some(released),
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: None,
+        trailer_streams: vec![],
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request {
+        Request { url, .. } if url.contains("/catalog/series/last%2Dvideos/lastVideosIds=") => {
+            future::ok(Box::new(ResourceResponse::MetasDetailed {
+                metas_detailed: vec![
+                    MetaItem {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        videos: vec![
+                            video("tt1:1:1", Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0)),
+                            video("tt1:1:2", Utc.ymd(2020, 1, 5).and_hms_milli(0, 0, 0, 0)),
+                        ],
+                        ..Default::default()
+                    },
+                    MetaItem {
+                        id: "tt2".to_owned(),
+                        r#type: "series".to_owned(),
+                        videos: vec![
+                            video("tt2:1:0", Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)),
+                            video("tt2:1:1", Utc.ymd(2020, 1, 3).and_hms_milli(0, 0, 0, 0)),
+                            video("tt2:1:2", Utc.ymd(2020, 1, 4).and_hms_milli(0, 0, 0, 0)),
+                        ],
+                        ..Default::default()
+                    },
+                ],
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn notification_video_ids(notifications: Notifications) -> Vec<String> {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 6).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![
+                        addon("https://first.addon/manifest.json"),
+                        addon("https://second.addon/manifest.json"),
+                    ],
+                    ..Default::default()
+                },
+                library: LibraryBucket::new(None, vec![library_item("tt1"), library_item("tt2")]),
+                ..Default::default()
+            },
+            notifications,
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Notifications),
+        })
+    });
+    let notification_video_ids = runtime
+        .model()
+        .unwrap()
+        .notifications
+        .notification_items
+        .iter()
+        .map(|notification_item| notification_item.video.id.to_owned())
+        .collect();
+    notification_video_ids
+}
+
+#[test]
+fn notifications_notification_items() {
+    assert_eq!(
+        notification_video_ids(Notifications::default()),
+        vec!["tt1:1:2", "tt2:1:2", "tt2:1:1", "tt1:1:1"],
+        "New videos deduped across addons and sorted by release date"
+    );
+}
+
+#[test]
+fn notifications_notification_items_count() {
+    assert_eq!(
+        notification_video_ids(Notifications::new(2)),
+        vec!["tt1:1:2", "tt2:1:2"],
+        "Newest videos limited to the items count"
+    );
+}
@@ -0,0 +1,3 @@
+mod next_stream;
+mod select_subtitles;
+mod streams_bucket;
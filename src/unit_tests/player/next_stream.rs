@@ -0,0 +1,182 @@
+use crate::models::ctx::Ctx;
+use crate::models::player::{Player, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{ResourcePath, ResourceRequest, ResourceResponse};
+use crate::types::profile::{Profile, Settings};
+use crate::types::resource::{MetaItem, Stream, StreamBehaviorHints, StreamSource, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+}
+
+fn stream(yt_id: &str, binge_group: Option<&str>) -> Stream {
+    Stream {
+        source: StreamSource::YouTube {
+            yt_id: yt_id.to_owned(),
+        },
+        title: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: StreamBehaviorHints {
+            binge_group: binge_group.map(ToOwned::to_owned),
+            ..Default::default()
+        },
+    }
+}
+
+fn video(id: &str) -> Video {
+    Video {
+        id: id.to_owned(),
+        title: "title".to_owned(),
+        released: None,
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: None,
+        trailer_streams: vec![],
+    }
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request.url.as_str() {
+        "https://transport_url/meta/series/tt1.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    id: "tt1".to_owned(),
+                    r#type: "series".to_owned(),
+                    videos: vec![video("tt1:1:1"), video("tt1:1:2")],
+                    ..Default::default()
+                },
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        "https://transport_url/stream/series/tt1%3A1%3A2.json" => {
+            future::ok(Box::new(ResourceResponse::Streams {
+                streams: vec![
+                    stream("other", Some("other")),
+                    stream("next", Some("group")),
+                ],
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn test_model(binge_watching: bool) -> TestModel {
+    TestModel {
+        ctx: Ctx {
+            profile: Profile {
+                settings: Settings {
+                    binge_watching,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        player: Default::default(),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>, binge_group: &str) {
+    let transport_url = Url::parse("https://transport_url/manifest.json").unwrap();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Selected {
+                stream: stream("current", Some(binge_group)),
+                stream_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("stream", "series", "tt1:1:1"),
+                )),
+                meta_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("meta", "series", "tt1"),
+                )),
+                subtitles_path: None,
+            })),
+        })
+    });
+}
+
+#[test]
+fn player_next_stream() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(true), Effects::none().unchanged(), 1000);
+    load(&runtime, "group");
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .player
+            .next_video
+            .as_ref()
+            .map(|video| video.id.as_str()),
+        Some("tt1:1:2"),
+        "Next video selected"
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.next_stream,
+        Some(stream("next", Some("group"))),
+        "Next stream of the same binge group selected"
+    );
+}
+
+#[test]
+fn player_next_stream_no_match() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(true), Effects::none().unchanged(), 1000);
+    load(&runtime, "unknown");
+    assert!(
+        runtime.model().unwrap().player.next_streams.is_some(),
+        "Next video streams requested"
+    );
+    assert_eq!(
+        runtime.model().unwrap().player.next_stream,
+        None,
+        "No next stream without a matching binge group"
+    );
+}
+
+#[test]
+fn player_next_stream_binge_watching_disabled() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(false), Effects::none().unchanged(), 1000);
+    load(&runtime, "group");
+    assert!(
+        runtime.model().unwrap().player.next_video.is_none(),
+        "No next video"
+    );
+    assert!(
+        runtime.model().unwrap().player.next_stream.is_none(),
+        "No next stream"
+    );
+    assert_eq!(
+        REQUESTS
+            .read()
+            .unwrap()
+            .iter()
+            .map(|request| request.url.as_str())
+            .collect::<Vec<_>>(),
+        vec!["https://transport_url/meta/series/tt1.json"],
+        "Next video streams not requested"
+    );
+}
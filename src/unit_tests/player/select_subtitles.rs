@@ -0,0 +1,130 @@
+use crate::constants::SUBTITLES_STORAGE_KEY;
+use crate::models::ctx::Ctx;
+use crate::models::player::{Player, Selected};
+use crate::runtime::msg::{Action, ActionLoad, ActionPlayer};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{ResourcePath, ResourceRequest, ResourceResponse};
+use crate::types::resource::{MetaItem, Stream, StreamSource};
+use crate::types::subtitles::{SubtitlesBucket, SubtitlesItem};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, STORAGE};
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request.url.as_str() {
+        "https://transport_url/meta/series/tt1.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    id: "tt1".to_owned(),
+                    r#type: "series".to_owned(),
+                    ..Default::default()
+                },
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>) {
+    let transport_url = Url::parse("https://transport_url/manifest.json").unwrap();
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Selected {
+                stream: Stream {
+                    source: StreamSource::YouTube {
+                        yt_id: "yt_id".to_owned(),
+                    },
+                    title: None,
+                    thumbnail: None,
+                    subtitles: vec![],
+                    behavior_hints: Default::default(),
+                },
+                stream_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("stream", "series", "tt1:1:1"),
+                )),
+                meta_request: Some(ResourceRequest::new(
+                    transport_url,
+                    ResourcePath::without_extra("meta", "series", "tt1"),
+                )),
+                subtitles_path: None,
+            })),
+        })
+    });
+}
+
+#[test]
+fn player_select_subtitles() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    load(&runtime);
+    assert!(
+        runtime.model().unwrap().player.subtitles_item.is_none(),
+        "No subtitles selected initially"
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Player(ActionPlayer::SelectSubtitles {
+                id: Some("id".to_owned()),
+                lang: Some("eng".to_owned()),
+                delay: Some(500),
+                size: Some(2),
+            }),
+        })
+    });
+    let subtitles_item = SubtitlesItem {
+        library_item_id: "tt1".to_owned(),
+        video_id: "tt1:1:1".to_owned(),
+        subtitles_id: Some("id".to_owned()),
+        subtitles_lang: Some("eng".to_owned()),
+        delay: Some(500),
+        size: Some(2),
+        mtime: *NOW.read().unwrap(),
+    };
+    assert_eq!(
+        runtime.model().unwrap().player.subtitles_item,
+        Some(subtitles_item.to_owned()),
+        "Selected subtitles updated in player"
+    );
+    let subtitles = serde_json::from_str::<SubtitlesBucket>(
+        &STORAGE.read().unwrap().get(SUBTITLES_STORAGE_KEY).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        subtitles.get("tt1", "tt1:1:1"),
+        Some(&subtitles_item),
+        "Selected subtitles persisted in storage"
+    );
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                subtitles,
+                ..Default::default()
+            },
+            player: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    load(&runtime);
+    assert_eq!(
+        runtime.model().unwrap().player.subtitles_item,
+        Some(subtitles_item),
+        "Selected subtitles restored from the persisted bucket"
+    );
+}
@@ -0,0 +1,166 @@
+use crate::constants::{STREAMS_MAX_COUNT, STREAMS_STORAGE_KEY};
+use crate::models::ctx::Ctx;
+use crate::models::player::{Player, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{ResourcePath, ResourceRequest, ResourceResponse};
+use crate::types::resource::{MetaItem, Stream, StreamSource};
+use crate::types::streams::{StreamsBucket, StreamsItem};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, STORAGE};
+use chrono::prelude::TimeZone;
+use chrono::{Duration, Utc};
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    player: Player,
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request.url.as_str() {
+        "https://transport_url/meta/series/tt1.json" => {
+            future::ok(Box::new(ResourceResponse::Meta {
+                meta: MetaItem {
+                    id: "tt1".to_owned(),
+                    r#type: "series".to_owned(),
+                    ..Default::default()
+                },
+                cache_hints: Default::default(),
+            }) as Box<dyn Any>)
+            .boxed_local()
+        }
+        _ => default_fetch_handler(request),
+    }
+}
+
+#[test]
+fn player_load_updates_streams_bucket() {
+    let transport_url = Url::parse("https://transport_url/manifest.json").unwrap();
+    let stream = Stream {
+        source: StreamSource::YouTube {
+            yt_id: "yt_id".to_owned(),
+        },
+        title: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Selected {
+                stream: stream.to_owned(),
+                stream_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("stream", "series", "tt1:1:1"),
+                )),
+                meta_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("meta", "series", "tt1"),
+                )),
+                subtitles_path: None,
+            })),
+        })
+    });
+    let streams_item = StreamsItem {
+        meta_id: "tt1".to_owned(),
+        video_id: "tt1:1:1".to_owned(),
+        stream,
+        transport_url,
+        mtime: *NOW.read().unwrap(),
+    };
+    assert_eq!(
+        runtime.model().unwrap().ctx.streams.get("tt1", "tt1:1:1"),
+        Some(&streams_item),
+        "Selected stream stored in the streams bucket"
+    );
+    assert_eq!(
+        serde_json::from_str::<StreamsBucket>(
+            &STORAGE.read().unwrap().get(STREAMS_STORAGE_KEY).unwrap()
+        )
+        .unwrap(),
+        runtime.model().unwrap().ctx.streams,
+        "Streams bucket persisted in storage"
+    );
+}
+
+#[test]
+fn player_load_streams_bucket_max_count() {
+    let transport_url = Url::parse("https://transport_url/manifest.json").unwrap();
+    let stream = Stream {
+        source: StreamSource::YouTube {
+            yt_id: "yt_id".to_owned(),
+        },
+        title: None,
+        thumbnail: None,
+        subtitles: vec![],
+        behavior_hints: Default::default(),
+    };
+    let mut streams = StreamsBucket::new(None);
+    for index in 0..STREAMS_MAX_COUNT {
+        streams.insert(StreamsItem {
+            meta_id: format!("tt{}", index + 2),
+            video_id: format!("tt{}", index + 2),
+            stream: stream.to_owned(),
+            transport_url: transport_url.to_owned(),
+            mtime: Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0) + Duration::minutes(index as i64),
+        });
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                streams,
+                ..Default::default()
+            },
+            player: Default::default(),
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Player(Selected {
+                stream,
+                stream_request: Some(ResourceRequest::new(
+                    transport_url.to_owned(),
+                    ResourcePath::without_extra("stream", "series", "tt1:1:1"),
+                )),
+                meta_request: Some(ResourceRequest::new(
+                    transport_url,
+                    ResourcePath::without_extra("meta", "series", "tt1"),
+                )),
+                subtitles_path: None,
+            })),
+        })
+    });
+    let streams = &runtime.model().unwrap().ctx.streams;
+    assert_eq!(
+        streams
+            .items
+            .values()
+            .map(|items| items.len())
+            .sum::<usize>(),
+        STREAMS_MAX_COUNT,
+        "Streams bucket capped"
+    );
+    assert!(
+        streams.get("tt1", "tt1:1:1").is_some(),
+        "Selected stream stored in the streams bucket"
+    );
+    assert!(
+        streams.get("tt2", "tt2").is_none(),
+        "Oldest stream evicted from the streams bucket"
+    );
+}
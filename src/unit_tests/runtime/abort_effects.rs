@@ -0,0 +1,220 @@
+use crate::models::common::Loadable;
+use crate::models::ctx::Ctx;
+use crate::models::meta_details::{MetaDetails, Selected};
+use crate::runtime::msg::{Action, ActionLoad, Internal, Msg};
+use crate::runtime::{Effects, Env, EnvError, Runtime, RuntimeAction, TryEnvFuture, UpdateWithCtx};
+use crate::types::addon::{
+    Descriptor, Manifest, ManifestResource, ResourcePath, ResourceRequest, ResourceResponse,
+};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItem;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER};
+use chrono::Duration;
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Default)]
+struct Requests {
+    results: Vec<ResourceRequest>,
+}
+
+impl UpdateWithCtx<TestEnv> for Requests {
+    fn update(&mut self, msg: &Msg, _: &Ctx) -> Effects {
+        match msg {
+            Msg::Action(Action::Load(ActionLoad::MetaDetails(selected))) => {
+                let request = ResourceRequest::new(
+                    Url::parse("https://transport_url/manifest.json").unwrap(),
+                    selected.meta_path.to_owned(),
+                );
+                Effects::future(
+                    TestEnv::sleep(Duration::seconds(1))
+                        .map(move |_| {
+                            Msg::Internal(Internal::ResourceRequestResult(
+                                request,
+                                Box::new(Err(EnvError::Fetch("error".to_owned()))),
+                            ))
+                        })
+                        .boxed_local(),
+                )
+                .unchanged()
+            }
+            Msg::Internal(Internal::ResourceRequestResult(request, _)) => {
+                self.results.push(request.to_owned());
+                Effects::none()
+            }
+            _ => Effects::none().unchanged(),
+        }
+    }
+}
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    requests: Requests,
+    meta_details: MetaDetails,
+}
+
+fn load(id: &str) -> Action {
+    Action::Load(ActionLoad::MetaDetails(Selected {
+        meta_path: ResourcePath::without_extra("meta", "movie", id),
+        stream_path: None,
+    }))
+}
+
+fn results(runtime: &Runtime<TestEnv, TestModel>) -> Vec<String> {
+    runtime
+        .model()
+        .unwrap()
+        .requests
+        .results
+        .iter()
+        .map(|request| request.path.id.to_owned())
+        .collect()
+}
+
+#[test]
+fn runtime_abort_effects_superseded_load() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Requests),
+            action: load("tt1"),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Requests),
+            action: load("tt2"),
+        });
+    });
+    TestEnv::advance_clock(Duration::seconds(1));
+    assert_eq!(
+        results(&runtime),
+        vec!["tt2"],
+        "Superseded load result never reached the model"
+    );
+}
+
+#[test]
+fn runtime_abort_effects_unload() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Requests),
+            action: load("tt1"),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Requests),
+            action: Action::Unload,
+        });
+    });
+    TestEnv::advance_clock(Duration::seconds(1));
+    assert!(
+        results(&runtime).is_empty(),
+        "Unloaded field result never reached the model"
+    );
+}
+
+#[test]
+fn runtime_abort_effects_unload_other_field() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(TestModel::default(), Effects::none().unchanged(), 1000);
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Requests),
+            action: load("tt1"),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::Ctx),
+            action: Action::Unload,
+        });
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Unload,
+        });
+    });
+    TestEnv::advance_clock(Duration::seconds(1));
+    assert_eq!(
+        results(&runtime),
+        vec!["tt1"],
+        "Effects of other fields are not aborted"
+    );
+}
+
+#[test]
+fn runtime_abort_effects_same_load() {
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request.url.as_str() {
+            "https://abort.addon/meta/movie/tt1.json" => {
+                future::ok(Box::new(ResourceResponse::Meta {
+                    meta: MetaItem {
+                        id: "tt1".to_owned(),
+                        r#type: "movie".to_owned(),
+                        ..Default::default()
+                    },
+                    cache_hints: Default::default(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                profile: Profile {
+                    addons: vec![Descriptor {
+                        manifest: Manifest {
+                            id: "id".to_owned(),
+                            version: Version::new(0, 0, 1),
+                            name: "name".to_owned(),
+                            contact_email: None,
+                            description: None,
+                            logo: None,
+                            background: None,
+                            types: vec!["movie".to_owned()],
+                            resources: vec![ManifestResource::Short("meta".to_owned())],
+                            id_prefixes: None,
+                            catalogs: vec![],
+                            addon_catalogs: vec![],
+                            behavior_hints: Default::default(),
+                        },
+                        transport_url: Url::parse("https://abort.addon/manifest.json").unwrap(),
+                        flags: Default::default(),
+                    }],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::MetaDetails),
+            action: load("tt1"),
+        });
+        runtime.dispatch(RuntimeAction {
+            field: Some(TestModelField::MetaDetails),
+            action: load("tt1"),
+        });
+    });
+    assert!(
+        matches!(
+            runtime.model().unwrap().meta_details.meta_items.first(),
+            Some(resource) if matches!(resource.content, Loadable::Ready(_))
+        ),
+        "Repeated identical load does not abort the pending request"
+    );
+}
@@ -0,0 +1,2 @@
+mod abort_effects;
+mod start_scheduler;
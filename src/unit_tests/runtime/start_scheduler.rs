@@ -0,0 +1,127 @@
+use crate::models::ctx::Ctx;
+use crate::models::notifications::Notifications;
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::ResourceResponse;
+use crate::types::library::{LibraryBucket, LibraryItem, LibraryItemState};
+use crate::types::resource::{MetaItem, SeriesInfo, Video};
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, NOW, REQUESTS};
+use chrono::prelude::TimeZone;
+use chrono::{Duration, Utc};
+use futures::{future, FutureExt};
+use std::any::Any;
+use stremio_derive::Model;
+
+#[test]
+fn runtime_startscheduler() {
+    #[derive(Model, Default)]
+    #[model(TestEnv)]
+    struct TestModel {
+        ctx: Ctx,
+        notifications: Notifications,
+    }
+    fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+        match request {
+            Request { url, method, .. }
+                if url == "https://v4-cinemeta.strem.io/catalog/series/last%2Dvideos/lastVideosIds=tt1.json"
+                    && method == "GET" =>
+            {
+                future::ok(Box::new(ResourceResponse::MetasDetailed {
+                    metas_detailed: vec![MetaItem {
+                        id: "tt1".to_owned(),
+                        r#type: "series".to_owned(),
+                        name: "name".to_owned(),
+                        videos: vec![Video {
+                            id: "tt1:1:1".to_owned(),
+                            title: "title".to_owned(),
+                            released: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
+                            overview: None,
+                            thumbnail: None,
+                            streams: vec![],
+                            series_info: Some(SeriesInfo {
+                                season: 1,
+                                episode: 1,
+                            }),
+                            trailer_streams: vec![],
+                        }],
+                        ..Default::default()
+                    }],
+                    cache_hints: Default::default(),
+                }) as Box<dyn Any>)
+                .boxed_local()
+            }
+            _ => default_fetch_handler(request),
+        }
+    }
+    let library_item = LibraryItem {
+        id: "tt1".to_owned(),
+        removed: false,
+        temp: false,
+        ctime: Some(Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)),
+        mtime: Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0),
+        state: LibraryItemState {
+            last_vid_released: Some(Utc.ymd(2019, 1, 1).and_hms_milli(0, 0, 0, 0)),
+            ..LibraryItemState::default()
+        },
+        name: "name".to_owned(),
+        r#type: "series".to_owned(),
+        poster: None,
+        poster_shape: Default::default(),
+        behavior_hints: Default::default(),
+    };
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    *NOW.write().unwrap() = Utc.ymd(2020, 1, 2).and_hms_milli(0, 0, 0, 0);
+    let (runtime, _rx) = Runtime::<TestEnv, _>::new(
+        TestModel {
+            ctx: Ctx {
+                library: LibraryBucket::new(None, vec![library_item]),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        Effects::none().unchanged(),
+        1000,
+    );
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Notifications),
+        });
+        runtime.start_scheduler(Duration::minutes(30));
+    });
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "One request has been sent"
+    );
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .notifications
+            .notification_items
+            .len(),
+        1,
+        "Notification items loaded"
+    );
+    TestEnv::advance_clock(Duration::minutes(30));
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        1,
+        "Notifications not refreshed before the refresh interval"
+    );
+    TestEnv::advance_clock(Duration::minutes(30));
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        2,
+        "Notifications refreshed after the refresh interval"
+    );
+    runtime.stop_scheduler();
+    TestEnv::advance_clock(Duration::hours(2));
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        2,
+        "Notifications not refreshed after the scheduler is stopped"
+    );
+}
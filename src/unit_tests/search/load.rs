@@ -0,0 +1,200 @@
+use crate::constants::SEARCH_DEBOUNCE_DELAY;
+use crate::models::ctx::Ctx;
+use crate::models::search::{Search, Selected};
+use crate::runtime::msg::{Action, ActionLoad};
+use crate::runtime::{Effects, Runtime, RuntimeAction, TryEnvFuture};
+use crate::types::addon::{
+    Descriptor, ExtraProp, Manifest, ManifestCatalog, ManifestExtra, ResourceResponse,
+};
+use crate::types::profile::Profile;
+use crate::types::resource::MetaItemPreview;
+use crate::unit_tests::{default_fetch_handler, Request, TestEnv, FETCH_HANDLER, REQUESTS};
+use futures::{future, FutureExt};
+use semver::Version;
+use std::any::Any;
+use stremio_derive::Model;
+use url::Url;
+
+#[derive(Model, Default)]
+#[model(TestEnv)]
+struct TestModel {
+    ctx: Ctx,
+    search: Search,
+}
+
+fn catalog(r#type: &str) -> ManifestCatalog {
+    ManifestCatalog {
+        r#type: r#type.to_owned(),
+        id: "top".to_owned(),
+        name: None,
+        extra: ManifestExtra::Full {
+            props: vec![ExtraProp {
+                name: "search".to_owned(),
+                is_required: true,
+                options: None,
+                options_limit: Default::default(),
+            }],
+        },
+    }
+}
+
+fn addon(transport_url: &str, catalogs: Vec<ManifestCatalog>) -> Descriptor {
+    Descriptor {
+        manifest: Manifest {
+            id: transport_url.to_owned(),
+            version: Version::new(0, 0, 1),
+            name: "name".to_owned(),
+            contact_email: None,
+            description: None,
+            logo: None,
+            background: None,
+            types: vec![],
+            resources: vec![],
+            id_prefixes: None,
+            catalogs,
+            addon_catalogs: vec![],
+            behavior_hints: Default::default(),
+        },
+        transport_url: Url::parse(transport_url).unwrap(),
+        flags: Default::default(),
+    }
+}
+
+fn meta_preview(id: &str, r#type: &str, popularity: Option<f64>) -> MetaItemPreview {
+    MetaItemPreview {
+        id: id.to_owned(),
+        r#type: r#type.to_owned(),
+        popularity,
+        ..Default::default()
+    }
+}
+
+fn metas(metas: Vec<MetaItemPreview>) -> TryEnvFuture<Box<dyn Any>> {
+    future::ok(Box::new(ResourceResponse::Metas {
+        metas,
+        cache_hints: Default::default(),
+    }) as Box<dyn Any>)
+    .boxed_local()
+}
+
+fn fetch_handler(request: Request) -> TryEnvFuture<Box<dyn Any>> {
+    match request.url.as_str() {
+        "https://first.addon/catalog/movie/top/search=query.json" => metas(vec![
+            meta_preview("movie1", "movie", Some(1.0)),
+            meta_preview("movie2", "movie", Some(5.0)),
+        ]),
+        "https://first.addon/catalog/series/top/search=query.json" => {
+            metas(vec![meta_preview("series1", "series", Some(10.0))])
+        }
+        "https://second.addon/catalog/movie/top/search=query.json" => metas(vec![
+            meta_preview("movie2", "movie", Some(5.0)),
+            meta_preview("movie3", "movie", None),
+        ]),
+        _ => default_fetch_handler(request),
+    }
+}
+
+fn test_model() -> TestModel {
+    TestModel {
+        ctx: Ctx {
+            profile: Profile {
+                addons: vec![
+                    addon(
+                        "https://first.addon/manifest.json",
+                        vec![catalog("series"), catalog("movie")],
+                    ),
+                    addon("https://second.addon/manifest.json", vec![catalog("movie")]),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        search: Default::default(),
+    }
+}
+
+fn load(runtime: &Runtime<TestEnv, TestModel>, query: &str) {
+    TestEnv::run(|| {
+        runtime.dispatch(RuntimeAction {
+            field: None,
+            action: Action::Load(ActionLoad::Search(Selected {
+                query: query.to_owned(),
+            })),
+        })
+    });
+}
+
+#[test]
+fn search_debounce() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    load(&runtime, "que");
+    TestEnv::advance_clock(*SEARCH_DEBOUNCE_DELAY / 2);
+    load(&runtime, "query");
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests sent before the debounce delay"
+    );
+    assert!(runtime.model().unwrap().search.loading, "Search is loading");
+    TestEnv::advance_clock(*SEARCH_DEBOUNCE_DELAY / 2);
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "Superseded query not requested"
+    );
+    TestEnv::advance_clock(*SEARCH_DEBOUNCE_DELAY / 2);
+    assert_eq!(
+        REQUESTS.read().unwrap().len(),
+        3,
+        "Latest query requested from all catalogs"
+    );
+    assert!(
+        !runtime.model().unwrap().search.loading,
+        "Search is not loading"
+    );
+    assert_eq!(
+        runtime.model().unwrap().ctx.search_history.recent().next(),
+        Some(&"query".to_owned()),
+        "Latest query stored in search history"
+    );
+}
+
+#[test]
+fn search_results() {
+    TestEnv::reset();
+    *FETCH_HANDLER.write().unwrap() = Box::new(fetch_handler);
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    load(&runtime, "query");
+    TestEnv::advance_clock(*SEARCH_DEBOUNCE_DELAY);
+    assert_eq!(
+        runtime
+            .model()
+            .unwrap()
+            .search
+            .results
+            .iter()
+            .map(|meta_item| meta_item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["movie2", "movie1", "movie3", "series1"],
+        "Results deduped and ranked by type priority and popularity"
+    );
+}
+
+#[test]
+fn search_empty_query() {
+    TestEnv::reset();
+    let (runtime, _rx) =
+        Runtime::<TestEnv, _>::new(test_model(), Effects::none().unchanged(), 1000);
+    load(&runtime, "");
+    TestEnv::advance_clock(*SEARCH_DEBOUNCE_DELAY);
+    assert!(
+        REQUESTS.read().unwrap().is_empty(),
+        "No requests sent for an empty query"
+    );
+    assert!(
+        !runtime.model().unwrap().search.loading,
+        "Search is not loading"
+    );
+}
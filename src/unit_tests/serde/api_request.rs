@@ -1,5 +1,5 @@
 use crate::types::api::{APIRequest, AuthRequest};
-use crate::types::profile::AuthKey;
+use crate::types::profile::{AuthKey, GDPRConsent, User};
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use serde_test::{assert_ser_tokens, Token};
 
@@ -11,6 +11,13 @@ fn api_request() {
             APIRequest::Logout {
                 auth_key: AuthKey::default(),
             },
+            APIRequest::GetUser {
+                auth_key: AuthKey::default(),
+            },
+            APIRequest::SaveUser {
+                auth_key: AuthKey::default(),
+                user: User::default(),
+            },
             APIRequest::AddonCollectionGet {
                 auth_key: AuthKey::default(),
                 update: true,
@@ -21,7 +28,7 @@ fn api_request() {
             },
         ],
         &[
-            vec![Token::Seq { len: Some(4) }],
+            vec![Token::Seq { len: Some(6) }],
             AuthRequest::default_tokens(),
             vec![
                 Token::Struct {
@@ -35,6 +42,41 @@ fn api_request() {
             AuthKey::default_tokens(),
             vec![
                 Token::StructEnd,
+                Token::Struct {
+                    name: "APIRequest",
+                    len: 2,
+                },
+                Token::Str("type"),
+                Token::Str("GetUser"),
+                Token::Str("authKey"),
+            ],
+            AuthKey::default_tokens(),
+            vec![
+                Token::StructEnd,
+                Token::Map { len: None },
+                Token::Str("type"),
+                Token::Str("SaveUser"),
+                Token::Str("authKey"),
+            ],
+            AuthKey::default_tokens(),
+            vec![
+                Token::Str("_id"),
+                Token::Str(""),
+                Token::Str("email"),
+                Token::Str(""),
+                Token::Str("fbId"),
+                Token::None,
+                Token::Str("avatar"),
+                Token::None,
+                Token::Str("lastModified"),
+                Token::Str("1970-01-01T00:00:00Z"),
+                Token::Str("dateRegistered"),
+                Token::Str("1970-01-01T00:00:00Z"),
+                Token::Str("gdpr_consent"),
+            ],
+            GDPRConsent::default_tokens(),
+            vec![
+                Token::MapEnd,
                 Token::Struct {
                     name: "APIRequest",
                     len: 3,
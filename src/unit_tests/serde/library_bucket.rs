@@ -1,5 +1,7 @@
 use crate::types::library::LibraryBucket;
-use serde_test::{assert_tokens, Token};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use serde_test::{assert_de_tokens, assert_tokens, Token};
 use std::collections::HashMap;
 
 #[test]
@@ -9,17 +11,19 @@ fn library_bucket() {
             LibraryBucket {
                 uid: Some("uid".to_owned()),
                 items: HashMap::new(),
+                last_synced: Some(Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0)),
             },
             LibraryBucket {
                 uid: None,
                 items: HashMap::new(),
+                last_synced: None,
             },
         ],
         &[
             Token::Seq { len: Some(2) },
             Token::Struct {
                 name: "LibraryBucket",
-                len: 2,
+                len: 3,
             },
             Token::Str("uid"),
             Token::Some,
@@ -27,18 +31,46 @@ fn library_bucket() {
             Token::Str("items"),
             Token::Map { len: Some(0) },
             Token::MapEnd,
+            Token::Str("last_synced"),
+            Token::Some,
+            Token::Str("2020-01-01T00:00:00Z"),
             Token::StructEnd,
             Token::Struct {
                 name: "LibraryBucket",
-                len: 2,
+                len: 3,
             },
             Token::Str("uid"),
             Token::None,
             Token::Str("items"),
             Token::Map { len: Some(0) },
             Token::MapEnd,
+            Token::Str("last_synced"),
+            Token::None,
             Token::StructEnd,
             Token::SeqEnd,
         ],
     );
 }
+
+#[test]
+fn library_bucket_de() {
+    assert_de_tokens(
+        &LibraryBucket {
+            uid: None,
+            items: HashMap::new(),
+            last_synced: None,
+        },
+        &[
+            Token::Struct {
+                name: "LibraryBucket",
+                len: 2,
+            },
+            Token::Str("uid"),
+            Token::None,
+            Token::Str("items"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::StructEnd,
+        ],
+    );
+}
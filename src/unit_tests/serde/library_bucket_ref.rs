@@ -7,11 +7,12 @@ fn library_bucket_ref() {
         &LibraryBucketRef {
             uid: &Some("uid".to_owned()),
             items: vec![].iter().cloned().collect(),
+            last_synced: &None,
         },
         &[
             Token::Struct {
                 name: "LibraryBucketRef",
-                len: 2,
+                len: 3,
             },
             Token::Str("uid"),
             Token::Some,
@@ -19,6 +20,8 @@ fn library_bucket_ref() {
             Token::Str("items"),
             Token::Map { len: Some(0) },
             Token::MapEnd,
+            Token::Str("last_synced"),
+            Token::None,
             Token::StructEnd,
         ],
     );
@@ -0,0 +1,24 @@
+use crate::types::library::LibraryExport;
+use serde_test::{assert_tokens, Token};
+
+#[test]
+fn library_export() {
+    assert_tokens(
+        &LibraryExport {
+            version: 1,
+            items: vec![],
+        },
+        &[
+            Token::Struct {
+                name: "LibraryExport",
+                len: 2,
+            },
+            Token::Str("version"),
+            Token::U32(1),
+            Token::Str("items"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+        ],
+    );
+}
@@ -182,3 +182,26 @@ fn library_item_state() {
         ],
     );
 }
+
+#[test]
+fn library_item_state_malformed_watched() {
+    let state = serde_json::from_str::<LibraryItemState>(
+        r#"{"timeWatched":0,"timeOffset":0,"overallTimeWatched":0,"timesWatched":0,"flaggedWatched":0,"duration":0,"watched":"video_id:eJxjBwAACAAI","noNotif":false}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        state.watched,
+        Some("video_id:eJxjBwAACAAI".to_owned()),
+        "malformed watched value kept"
+    );
+    assert_eq!(
+        state.watched_field(),
+        None,
+        "malformed watched value not parsed"
+    );
+    assert_eq!(
+        serde_json::to_value(&state).unwrap()["watched"],
+        "video_id:eJxjBwAACAAI",
+        "malformed watched value serialized back"
+    );
+}
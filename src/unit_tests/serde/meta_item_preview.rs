@@ -14,6 +14,7 @@ fn meta_item_preview() {
                 name: "name".to_owned(),
                 poster: Some("poster".to_owned()),
                 logo: Some("logo".to_owned()),
+                popularity: Some(1.0),
                 description: Some("description".to_owned()),
                 release_info: Some("release_info".to_owned()),
                 runtime: Some("runtime".to_owned()),
@@ -28,6 +29,7 @@ fn meta_item_preview() {
                 name: "name".to_owned(),
                 poster: None,
                 logo: None,
+                popularity: None,
                 description: None,
                 release_info: None,
                 runtime: None,
@@ -42,7 +44,7 @@ fn meta_item_preview() {
                 Token::Seq { len: Some(2) },
                 Token::Struct {
                     name: "MetaItemPreview",
-                    len: 12,
+                    len: 13,
                 },
                 Token::Str("id"),
                 Token::Str("id"),
@@ -56,6 +58,9 @@ fn meta_item_preview() {
                 Token::Str("logo"),
                 Token::Some,
                 Token::Str("logo"),
+                Token::Str("popularity"),
+                Token::Some,
+                Token::F64(1.0),
                 Token::Str("description"),
                 Token::Some,
                 Token::Str("description"),
@@ -82,7 +87,7 @@ fn meta_item_preview() {
                 Token::StructEnd,
                 Token::Struct {
                     name: "MetaItemPreview",
-                    len: 12,
+                    len: 13,
                 },
                 Token::Str("id"),
                 Token::Str("id"),
@@ -94,6 +99,8 @@ fn meta_item_preview() {
                 Token::None,
                 Token::Str("logo"),
                 Token::None,
+                Token::Str("popularity"),
+                Token::None,
                 Token::Str("description"),
                 Token::None,
                 Token::Str("releaseInfo"),
@@ -123,6 +130,7 @@ fn meta_item_preview() {
             name: "".to_owned(),
             poster: None,
             logo: None,
+            popularity: None,
             description: None,
             release_info: None,
             runtime: None,
@@ -18,6 +18,7 @@ mod gdpr_consent;
 mod gdpr_consent_request;
 mod library_bucket;
 mod library_bucket_ref;
+mod library_export;
 mod library_item;
 mod library_item_behavior_hints;
 mod library_item_modified;
@@ -32,17 +33,21 @@ mod manifest_resource;
 mod meta_item;
 mod meta_item_behavior_hints;
 mod meta_item_preview;
+mod outbox_bucket;
 mod poster_shape;
 mod profile;
 mod resource_path;
 mod resource_request;
 mod resource_response;
+mod search_history_bucket;
 mod series_info;
 mod settings;
 mod stream;
 mod stream_source;
+mod streams_bucket;
 mod subtitles;
 mod success_response;
 mod r#true;
 mod user;
 mod video;
+mod watched_field;
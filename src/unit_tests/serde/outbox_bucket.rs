@@ -0,0 +1,64 @@
+use crate::types::outbox::OutboxBucket;
+use serde_test::{assert_tokens, Token};
+use std::collections::HashMap;
+
+#[test]
+fn outbox_bucket() {
+    assert_tokens(
+        &vec![
+            OutboxBucket {
+                uid: Some("uid".to_owned()),
+                library_items: HashMap::new(),
+                addons: Some(vec![]),
+                attempts: 1,
+                retry_after: None,
+            },
+            OutboxBucket {
+                uid: None,
+                library_items: HashMap::new(),
+                addons: None,
+                attempts: 0,
+                retry_after: None,
+            },
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Struct {
+                name: "OutboxBucket",
+                len: 5,
+            },
+            Token::Str("uid"),
+            Token::Some,
+            Token::Str("uid"),
+            Token::Str("libraryItems"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("addons"),
+            Token::Some,
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("attempts"),
+            Token::U32(1),
+            Token::Str("retryAfter"),
+            Token::None,
+            Token::StructEnd,
+            Token::Struct {
+                name: "OutboxBucket",
+                len: 5,
+            },
+            Token::Str("uid"),
+            Token::None,
+            Token::Str("libraryItems"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("addons"),
+            Token::None,
+            Token::Str("attempts"),
+            Token::U32(0),
+            Token::Str("retryAfter"),
+            Token::None,
+            Token::StructEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
@@ -10,11 +10,13 @@ fn profile() {
                 auth: Some(Auth::default()),
                 addons: vec![],
                 settings: Settings::default(),
+                settings_last_modified: None,
             },
             Profile {
                 auth: None,
                 addons: vec![],
                 settings: Settings::default(),
+                settings_last_modified: None,
             },
         ],
         &[
@@ -22,7 +24,7 @@ fn profile() {
                 Token::Seq { len: Some(2) },
                 Token::Struct {
                     name: "Profile",
-                    len: 3,
+                    len: 4,
                 },
                 Token::Str("auth"),
                 Token::Some,
@@ -36,10 +38,12 @@ fn profile() {
             ],
             Settings::default_tokens(),
             vec![
+                Token::Str("settings_last_modified"),
+                Token::None,
                 Token::StructEnd,
                 Token::Struct {
                     name: "Profile",
-                    len: 3,
+                    len: 4,
                 },
                 Token::Str("auth"),
                 Token::None,
@@ -49,7 +53,12 @@ fn profile() {
                 Token::Str("settings"),
             ],
             Settings::default_tokens(),
-            vec![Token::StructEnd, Token::SeqEnd],
+            vec![
+                Token::Str("settings_last_modified"),
+                Token::None,
+                Token::StructEnd,
+                Token::SeqEnd,
+            ],
         ]
         .concat(),
     );
@@ -58,6 +67,7 @@ fn profile() {
             auth: None,
             addons: vec![],
             settings: Settings::default(),
+            settings_last_modified: None,
         },
         &[
             vec![
@@ -1,4 +1,4 @@
-use crate::types::addon::ResourceResponse;
+use crate::types::addon::{ResourceCacheHints, ResourceResponse};
 use crate::types::resource::MetaItem;
 use crate::unit_tests::serde::default_tokens_ext::DefaultTokens;
 use serde_test::{assert_tokens, Token};
@@ -7,72 +7,122 @@ use serde_test::{assert_tokens, Token};
 fn resource_response() {
     assert_tokens(
         &vec![
-            ResourceResponse::Metas { metas: vec![] },
+            ResourceResponse::Metas {
+                metas: vec![],
+                cache_hints: Default::default(),
+            },
             ResourceResponse::MetasDetailed {
                 metas_detailed: vec![],
+                cache_hints: Default::default(),
             },
             ResourceResponse::Meta {
                 meta: MetaItem::default(),
+                cache_hints: Default::default(),
+            },
+            ResourceResponse::Streams {
+                streams: vec![],
+                cache_hints: Default::default(),
+            },
+            ResourceResponse::Subtitles {
+                subtitles: vec![],
+                cache_hints: Default::default(),
+            },
+            ResourceResponse::Addons {
+                addons: vec![],
+                cache_hints: Default::default(),
             },
-            ResourceResponse::Streams { streams: vec![] },
-            ResourceResponse::Subtitles { subtitles: vec![] },
-            ResourceResponse::Addons { addons: vec![] },
         ],
         &[
             vec![
                 Token::Seq { len: Some(6) },
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::Map { len: None },
                 Token::Str("metas"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::MapEnd,
+                Token::Map { len: None },
                 Token::Str("metasDetailed"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::MapEnd,
+                Token::Map { len: None },
                 Token::Str("meta"),
             ],
             MetaItem::default_tokens(),
             vec![
-                Token::StructEnd,
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::MapEnd,
+                Token::Map { len: None },
                 Token::Str("streams"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::MapEnd,
+                Token::Map { len: None },
                 Token::Str("subtitles"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
-                Token::Struct {
-                    name: "ResourceResponse",
-                    len: 1,
-                },
+                Token::MapEnd,
+                Token::Map { len: None },
                 Token::Str("addons"),
                 Token::Seq { len: Some(0) },
                 Token::SeqEnd,
-                Token::StructEnd,
+                Token::MapEnd,
                 Token::SeqEnd,
             ],
         ]
         .concat(),
     );
 }
+
+#[test]
+fn resource_response_with_cache_hints() {
+    assert_tokens(
+        &ResourceResponse::Streams {
+            streams: vec![],
+            cache_hints: ResourceCacheHints {
+                cache_max_age: Some(3600),
+                stale_revalidate: Some(600),
+                stale_error: None,
+            },
+        },
+        &[
+            Token::Map { len: None },
+            Token::Str("streams"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("cacheMaxAge"),
+            Token::Some,
+            Token::U32(3600),
+            Token::Str("staleRevalidate"),
+            Token::Some,
+            Token::U32(600),
+            Token::MapEnd,
+        ],
+    );
+}
+
+#[test]
+fn resource_response_with_lenient_cache_hints() {
+    assert_eq!(
+        serde_json::from_str::<ResourceResponse>(
+            r#"{"streams":[],"cacheMaxAge":3600.5,"staleRevalidate":-1,"staleError":1e20}"#
+        )
+        .unwrap(),
+        ResourceResponse::Streams {
+            streams: vec![],
+            cache_hints: ResourceCacheHints {
+                cache_max_age: Some(3600),
+                stale_revalidate: Some(0),
+                stale_error: Some(u32::MAX),
+            },
+        },
+        "Numbers are clamped to u32"
+    );
+    assert_eq!(
+        serde_json::from_str::<ResourceResponse>(r#"{"streams":[],"cacheMaxAge":"x"}"#).unwrap(),
+        ResourceResponse::Streams {
+            streams: vec![],
+            cache_hints: ResourceCacheHints::default(),
+        },
+        "Invalid values are ignored"
+    );
+}
@@ -0,0 +1,53 @@
+use crate::types::search_history::SearchHistoryBucket;
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use serde_test::{assert_tokens, Token};
+use std::collections::HashMap;
+
+#[test]
+fn search_history_bucket() {
+    assert_tokens(
+        &vec![
+            SearchHistoryBucket {
+                uid: Some("uid".to_owned()),
+                items: vec![(
+                    "query".to_owned(),
+                    Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+                )]
+                .into_iter()
+                .collect(),
+            },
+            SearchHistoryBucket {
+                uid: None,
+                items: HashMap::new(),
+            },
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Struct {
+                name: "SearchHistoryBucket",
+                len: 2,
+            },
+            Token::Str("uid"),
+            Token::Some,
+            Token::Str("uid"),
+            Token::Str("items"),
+            Token::Map { len: Some(1) },
+            Token::Str("query"),
+            Token::Str("2020-01-01T00:00:00Z"),
+            Token::MapEnd,
+            Token::StructEnd,
+            Token::Struct {
+                name: "SearchHistoryBucket",
+                len: 2,
+            },
+            Token::Str("uid"),
+            Token::None,
+            Token::Str("items"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::StructEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
@@ -0,0 +1,44 @@
+use crate::types::streams::StreamsBucket;
+use serde_test::{assert_tokens, Token};
+use std::collections::HashMap;
+
+#[test]
+fn streams_bucket() {
+    assert_tokens(
+        &vec![
+            StreamsBucket {
+                uid: Some("uid".to_owned()),
+                items: HashMap::new(),
+            },
+            StreamsBucket {
+                uid: None,
+                items: HashMap::new(),
+            },
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Struct {
+                name: "StreamsBucket",
+                len: 2,
+            },
+            Token::Str("uid"),
+            Token::Some,
+            Token::Str("uid"),
+            Token::Str("items"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::StructEnd,
+            Token::Struct {
+                name: "StreamsBucket",
+                len: 2,
+            },
+            Token::Str("uid"),
+            Token::None,
+            Token::Str("items"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::StructEnd,
+            Token::SeqEnd,
+        ],
+    );
+}
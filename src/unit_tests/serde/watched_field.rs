@@ -0,0 +1,99 @@
+use crate::types::library::LibraryItemState;
+use crate::types::resource::Video;
+use crate::types::watched_bitfield::{WatchedBitField, WatchedField};
+use chrono::prelude::TimeZone;
+use chrono::Utc;
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+#[test]
+fn watched_field() {
+    assert_tokens(
+        &vec![
+            "tt2934286:1:3:3:eJxjBwAACAAI"
+                .parse::<WatchedField>()
+                .unwrap(),
+            "video_id:1:eJwDAAAAAAE=".parse::<WatchedField>().unwrap(),
+        ],
+        &[
+            Token::Seq { len: Some(2) },
+            Token::Str("tt2934286:1:3:3:eJxjBwAACAAI"),
+            Token::Str("video_id:1:eJwDAAAAAAE="),
+            Token::SeqEnd,
+        ],
+    );
+    assert_de_tokens_error::<WatchedField>(
+        &[Token::Str("video_id:eJxjBwAACAAI")],
+        "Invalid anchor length",
+    );
+}
+
+#[test]
+fn watched_field_with_videos() {
+    let watched = "tt2934286:1:3:3:eJxjBwAACAAI"
+        .parse::<WatchedField>()
+        .unwrap();
+    let video_ids = vec![
+        "tt2934286:1:1".to_owned(),
+        "tt2934286:1:2".to_owned(),
+        "tt2934286:1:3".to_owned(),
+        "tt2934286:1:4".to_owned(),
+        "tt2934286:1:5".to_owned(),
+    ];
+    let watched_bitfield = WatchedBitField::new(Some(&watched), video_ids.to_owned());
+    assert!(
+        watched_bitfield.get_video("tt2934286:1:3") && !watched_bitfield.get_video("tt2934286:1:4"),
+        "watched videos restored"
+    );
+    assert_eq!(
+        WatchedField::from(&watched_bitfield).to_string(),
+        "tt2934286:1:3:3:eJxjBwAACAAI",
+        "anchored to the last watched video"
+    );
+    let mut video_ids_with_prequel = video_ids;
+    video_ids_with_prequel.insert(0, "tt2934286:0:1".to_owned());
+    let mut watched_bitfield = WatchedBitField::new(Some(&watched), video_ids_with_prequel);
+    assert!(
+        !watched_bitfield.get_video("tt2934286:0:1")
+            && watched_bitfield.get_video("tt2934286:1:1")
+            && watched_bitfield.get_video("tt2934286:1:3")
+            && !watched_bitfield.get_video("tt2934286:1:4"),
+        "watched videos re-anchored"
+    );
+    watched_bitfield.set_video("tt2934286:1:5", true);
+    assert_eq!(
+        WatchedField::from(&watched_bitfield).anchor_video,
+        "tt2934286:1:5",
+        "anchor moved to the last watched video"
+    );
+}
+
+#[test]
+fn watched_field_with_missing_anchor() {
+    let video = |id: &str| Video {
+        id: id.to_owned(),
+        title: Default::default(),
+        released: None,
+        overview: None,
+        thumbnail: None,
+        streams: vec![],
+        series_info: None,
+        trailer_streams: vec![],
+    };
+    let mut state = LibraryItemState {
+        video_id: Some("tt2934286:2:1".to_owned()),
+        watched: Some("tt2934286:1:3:3:eJxjBwAACAAI".to_owned()),
+        ..LibraryItemState::default()
+    };
+    state.mark_videos_as_watched(
+        &[video("tt2934286:2:1"), video("tt2934286:2:2")],
+        vec!["tt2934286:2:1"],
+        true,
+        Utc.ymd(2020, 1, 1).and_hms_milli(0, 0, 0, 0),
+    );
+    assert_eq!(
+        state.watched,
+        Some("tt2934286:1:3:3:eJxjBwAACAAI".to_owned()),
+        "watched value with a missing anchor kept"
+    );
+    assert_eq!(state.flagged_watched, 1, "current video marked as watched");
+}
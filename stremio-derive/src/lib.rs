@@ -78,7 +78,7 @@ pub fn model_derive(input: TokenStream) -> TokenStream {
                 .rev()
                 .collect::<Vec<_>>();
             TokenStream::from(quote! {
-                #[derive(serde::Deserialize)]
+                #[derive(Clone, PartialEq, serde::Deserialize)]
                 #[serde(rename_all = "snake_case")]
                 pub enum #field_enum_ident {
                     #(#field_enum_variant_idents),*